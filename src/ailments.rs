@@ -0,0 +1,189 @@
+use crate::{HashMap, effects::ActiveEffects, items::Amount, stats::StatModifier};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// the kinds of ailments there are. what each of them does is decided by its `AilmentAsset`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+)]
+pub enum StatusAilment {
+    Poison,
+    Bleed,
+    Stun,
+    Blind,
+    Silence,
+}
+
+impl StatusAilment {
+    /// where the asset describing this ailment lives, ie. "ailments/poison.ailment.ron".
+    pub fn asset_path(&self) -> String {
+        format!("ailments/{}.ailment.ron", self.to_string().to_lowercase())
+    }
+}
+
+/// things an ailment can stop the player (or a mob) from doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hindrance {
+    /// lose your turn in a fight.
+    Acting,
+    /// can't use items.
+    UsingItems,
+}
+
+/// how the ailment changes the way zones are described to the player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ZoneText {
+    #[default]
+    Clear,
+    /// only some of the words make it through. `legible` is the share (0.0 - 1.0) that do.
+    Garbled { legible: f32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Asset, TypePath)]
+pub struct AilmentAsset {
+    pub name: String,
+    /// flavor text that describes the ailment
+    pub description: String,
+    /// HP lost every second (or turn) while afflicted.
+    #[serde(default)]
+    pub damage: Option<Amount>,
+    /// applied to the status layer of the afflicted's stats.
+    #[serde(default)]
+    pub stat_modifiers: Vec<StatModifier>,
+    #[serde(default)]
+    pub prevents: Vec<Hindrance>,
+    #[serde(default)]
+    pub zone_text: ZoneText,
+}
+
+#[derive(Debug, Clone, Default, Resource)]
+pub struct Ailments(pub HashMap<String, Handle<AilmentAsset>>);
+
+/// looks up what an ailment does.
+#[derive(SystemParam)]
+pub struct AilmentData<'w> {
+    ailments: Res<'w, Ailments>,
+    assets: Res<'w, Assets<AilmentAsset>>,
+}
+
+impl AilmentData<'_> {
+    pub fn get(&self, ailment: StatusAilment) -> Option<&AilmentAsset> {
+        self.assets.get(self.ailments.0.get(&ailment.asset_path())?)
+    }
+
+    /// true if any of the ailments in `effects` prevent the hindrance.
+    pub fn hinders(&self, effects: &ActiveEffects, hindrance: Hindrance) -> bool {
+        effects.ailments().any(|ailment| {
+            self.get(ailment)
+                .is_some_and(|asset| asset.prevents.contains(&hindrance))
+        })
+    }
+
+    /// the stat modifiers of every ailment in `effects`.
+    pub fn modifiers(&self, effects: &ActiveEffects) -> Vec<StatModifier> {
+        effects
+            .ailments()
+            .filter_map(|ailment| self.get(ailment))
+            .flat_map(|asset| asset.stat_modifiers.iter().cloned())
+            .collect()
+    }
+
+    pub fn damage(&self, ailment: StatusAilment) -> Option<Amount> {
+        self.get(ailment)?.damage.clone()
+    }
+
+    /// how legible zone text is with the ailments in `effects`. None when it isn't affected.
+    pub fn legibility(&self, effects: &ActiveEffects) -> Option<f32> {
+        effects
+            .ailments()
+            .filter_map(|ailment| match self.get(ailment)?.zone_text {
+                ZoneText::Clear => None,
+                ZoneText::Garbled { legible } => Some(legible),
+            })
+            .reduce(f32::min)
+    }
+}
+
+/// the random number generator for procs, ie. a weapon inflicting an ailment on a hit, and for
+/// the coins a mob drops. kept apart from `EncounterRng` so how a fight goes doesn't change which
+/// mobs a seeded game rolls.
+#[derive(Resource, Debug, Clone)]
+pub struct ProcRng(pub StdRng);
+
+impl ProcRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for ProcRng {
+    fn default() -> Self {
+        Self(StdRng::from_os_rng())
+    }
+}
+
+/// rolls for an effect with a `chance` (0.0 - 1.0) to happen.
+pub fn roll_proc<R: Rng>(rng: &mut R, chance: f32) -> bool {
+    chance >= 1.0 || (chance > 0.0 && rng.random::<f32>() < chance)
+}
+
+/// blanks out words of the text, keeping about `legible` of them. the same text always comes out
+/// the same way, so looking around twice doesn't reveal more of it.
+pub fn obscure(text: &str, legible: f32) -> String {
+    text.split(' ')
+        .enumerate()
+        .map(|(i, word)| {
+            let roll = (fxhash::hash64(&(i, word)) % 1000) as f32 / 1000.0;
+
+            if roll < legible {
+                word.to_string()
+            } else {
+                word.chars()
+                    .map(|c| if c.is_alphanumeric() { '#' } else { c })
+                    .collect()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StatusAilment, obscure};
+
+    #[test]
+    fn obscured_text_keeps_its_shape() {
+        let text = "In front of you there is a tall wrought iron gate.";
+
+        assert_eq!(obscure(text, 1.0), text);
+        assert_eq!(
+            obscure(text, 0.0),
+            "## ##### ## ### ##### ## # #### ####### #### ####."
+        );
+
+        let half = obscure(text, 0.5);
+        assert_eq!(half, obscure(text, 0.5), "obscuring should be stable");
+        assert_eq!(half.len(), text.len());
+    }
+
+    #[test]
+    fn ailment_asset_paths() {
+        assert_eq!(
+            StatusAilment::Poison.asset_path(),
+            "ailments/poison.ailment.ron"
+        );
+    }
+}
@@ -0,0 +1,617 @@
+use crate::{
+    NewZone, Notification, NotificationLevel, TurnTaken,
+    ailments::{AilmentData, Hindrance, ProcRng, roll_proc},
+    commands::commands::BattleCmd,
+    effects::{ActiveEffect, ActiveEffects, EffectKind},
+    equipment::{Equipment, repair_equipped, wear_weapons},
+    items::{
+        EquipmentEffect, InventoryItems, ItemAsset, ItemType, remove_from_stack, repair_amount,
+    },
+    leveling::GainXp,
+    mobs::{MobAsset, MobId, Mobs},
+    nouns::{Disambiguation, NounPhrase, PendingQuestion, Picked, pick},
+    player::{CombatStats, EffectiveStats, Health, Player, SPAWN_ZONE, apply_consumable},
+    state::{GameState, MainState},
+    stats::apply_layer,
+    ui::update::{UpdateLookSectionText, UpdateMainSectionText},
+    wallet::Purse,
+    zones::Location,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::Rng;
+
+/// how many lines of the battle log are shown in the battle view.
+const LOG_LINES: usize = 8;
+
+/// starts a fight with a mob.
+#[derive(Message, Debug, Clone)]
+pub struct StartBattle {
+    pub mob: MobId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleOutcome {
+    Victory,
+    Defeat,
+    Fled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    Player,
+    Mob,
+}
+
+/// the state of the fight that is currently going on.
+#[derive(Resource, Debug, Clone)]
+pub struct Encounter {
+    pub mob_id: MobId,
+    pub mob_name: String,
+    pub mob_stats: CombatStats,
+    pub mob_hp: f32,
+    /// the ailments on the mob. these tick once a round.
+    pub mob_effects: ActiveEffects,
+    /// what the mobs hits can inflict on the player.
+    pub mob_on_hit: Vec<EquipmentEffect>,
+    /// the (min, max) coins the mob drops when beaten.
+    pub mob_coins: (u32, u32),
+    /// the XP the player gets for winning.
+    pub mob_xp: u32,
+    /// set when the player defends, halves the damage of the next hit they take this round.
+    pub player_defending: bool,
+    pub round: u32,
+    pub log: Vec<String>,
+    pub outcome: Option<BattleOutcome>,
+}
+
+impl Encounter {
+    pub fn new(mob_id: MobId, mob: &MobAsset) -> Self {
+        let mob_stats = CombatStats::from(mob);
+
+        Self {
+            mob_id,
+            mob_name: mob.name.clone(),
+            mob_stats,
+            mob_hp: mob_stats.hp,
+            mob_effects: ActiveEffects::default(),
+            mob_on_hit: mob.effects.clone(),
+            mob_coins: mob.coins,
+            mob_xp: mob.xp,
+            player_defending: false,
+            round: 0,
+            log: vec![format!("A {} attacks!", mob.name)],
+            outcome: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BattlePlugin;
+
+impl Plugin for BattlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<StartBattle>()
+            .add_systems(Update, start_battle.run_if(in_state(MainState::InGame)))
+            .add_systems(
+                Update,
+                (handle_battle_cmd, render_battle)
+                    .chain()
+                    .run_if(in_state(GameState::Battle)),
+            )
+            .add_systems(OnExit(GameState::Battle), end_battle);
+    }
+}
+
+/// how much damage an attack does. defence only ever softens a blow, it never fully blocks it.
+pub fn damage(attack: f32, defence: f32) -> f32 {
+    if attack <= 0.0 {
+        return 1.0;
+    }
+
+    (attack * attack / (attack + defence.max(0.0)))
+        .round()
+        .max(1.0)
+}
+
+/// who acts first in a round. the faster combatant goes first, the player wins ties.
+pub fn turn_order(player_speed: f32, mob_speed: f32) -> [Turn; 2] {
+    if player_speed >= mob_speed {
+        [Turn::Player, Turn::Mob]
+    } else {
+        [Turn::Mob, Turn::Player]
+    }
+}
+
+/// the chance of getting away from a fight. the faster the player is next to the mob the better,
+/// evenly matched is a coin flip.
+pub fn flee_chance(player_speed: f32, mob_speed: f32) -> f32 {
+    let (player_speed, mob_speed) = (player_speed.max(0.0), mob_speed.max(0.0));
+
+    if player_speed + mob_speed <= 0.0 {
+        return 0.5;
+    }
+
+    player_speed / (player_speed + mob_speed)
+}
+
+/// rolls for each ailment an attack can inflict, returning the ones that took hold.
+fn roll_on_hit<R: Rng>(
+    source: &str,
+    effects: &[EquipmentEffect],
+    rng: &mut R,
+) -> Vec<ActiveEffect> {
+    effects
+        .iter()
+        .filter_map(|effect| match effect {
+            EquipmentEffect::InflictStatus {
+                ailment,
+                duration,
+                chance,
+            } if roll_proc(rng, *chance) => Some(ActiveEffect::new(
+                source,
+                EffectKind::Ailment { ailment: *ailment },
+                *duration,
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// the names of the ailments in `effects`, ie. " [Poison, Stun]". empty if there are none.
+fn ailment_tags(effects: &ActiveEffects) -> String {
+    let ailments: Vec<String> = effects
+        .ailments()
+        .map(|ailment| ailment.to_string())
+        .collect();
+
+    if ailments.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", ailments.join(", "))
+    }
+}
+
+fn start_battle(
+    mut cmds: Commands,
+    mut start_evs: MessageReader<StartBattle>,
+    mobs: Res<Mobs>,
+    mob_assets: Res<Assets<MobAsset>>,
+    game_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut look_event: MessageWriter<UpdateLookSectionText>,
+) {
+    for ev in start_evs.read() {
+        if !matches!(game_state.get(), GameState::Adventure | GameState::Dungeon) {
+            warn!("can't start a battle with {} from {game_state:?}", ev.mob);
+            continue;
+        }
+
+        let Some(mob) = mobs.0.get(&ev.mob).and_then(|mob| mob_assets.get(mob)) else {
+            error!("tried to start a battle with unknown mob {:?}", ev.mob);
+            continue;
+        };
+
+        info!("starting a battle with {}", ev.mob);
+        cmds.insert_resource(Encounter::new(ev.mob.clone(), mob));
+        look_event.write(UpdateLookSectionText(String::new()));
+        next_state.set(GameState::Battle);
+        // only one fight at a time.
+        break;
+    }
+}
+
+/// the players side of a fight, them & their gear.
+#[derive(SystemParam)]
+struct Fighter<'w, 's> {
+    cmds: Commands<'w, 's>,
+    player: Query<
+        'w,
+        's,
+        (
+            &'static EffectiveStats,
+            &'static mut Health,
+            &'static mut ActiveEffects,
+            &'static mut Equipment,
+        ),
+        With<Player>,
+    >,
+    inventory: InventoryItems<'w, 's>,
+    notification_ev: MessageWriter<'w, Notification>,
+}
+
+/// what a round of fighting does outside of the fight. a turn passes, & once the fight is over
+/// the player is rewarded & sent on their way.
+#[derive(SystemParam)]
+struct Aftermath<'w, 's> {
+    turn_ev: MessageWriter<'w, TurnTaken>,
+    location: ResMut<'w, Location>,
+    next_state: ResMut<'w, NextState<GameState>>,
+    purse: Purse<'w, 's>,
+    xp_ev: MessageWriter<'w, GainXp>,
+}
+
+impl Aftermath<'_, '_> {
+    /// ends the fight if it has an outcome. a win pays out, a loss sends the player back to spawn
+    /// with their health restored to `max_hp`.
+    fn settle(
+        &mut self,
+        encounter: &mut Encounter,
+        health: &mut Health,
+        max_hp: f32,
+        rng: &mut impl Rng,
+    ) {
+        match encounter.outcome {
+            Some(BattleOutcome::Defeat) => {
+                health.0 = max_hp;
+                self.location.0 = SPAWN_ZONE.into();
+                self.next_state.set(GameState::Adventure);
+            }
+            Some(BattleOutcome::Victory) => {
+                let (min, max) = encounter.mob_coins;
+                let dropped = rng.random_range(min..=max.max(min));
+
+                if dropped > 0 {
+                    let line = format!("The {} dropped {dropped} coins.", encounter.mob_name);
+                    encounter.log.push(line);
+                    self.purse
+                        .earn(dropped, format!("looted the {}", encounter.mob_name));
+                }
+
+                if encounter.mob_xp > 0 {
+                    let line = format!("You gain {} XP.", encounter.mob_xp);
+                    encounter.log.push(line);
+                    self.xp_ev.write(GainXp {
+                        amount: encounter.mob_xp,
+                        source: format!("defeating the {}", encounter.mob_name),
+                    });
+                }
+
+                self.next_state.set(GameState::Adventure);
+            }
+            Some(_) => self.next_state.set(GameState::Adventure),
+            None => {}
+        }
+    }
+}
+
+fn handle_battle_cmd(
+    mut battle_cmds: MessageReader<BattleCmd>,
+    mut encounter: ResMut<Encounter>,
+    fighter: Fighter,
+    mut aftermath: Aftermath,
+    mut rng: ResMut<ProcRng>,
+    ailments: AilmentData,
+    mut question: ResMut<PendingQuestion>,
+) {
+    let Fighter {
+        mut cmds,
+        mut player,
+        inventory,
+        mut notification_ev,
+    } = fighter;
+
+    for cmd in battle_cmds.read() {
+        if encounter.outcome.is_some() {
+            continue;
+        }
+
+        let Ok((stats, mut health, mut active, mut equipment)) = player.single_mut() else {
+            error!("there is no player to fight with");
+            continue;
+        };
+        let stats = stats.0;
+
+        // whatever the players weapons can inflict on a hit.
+        let on_hit: Vec<EquipmentEffect> = equipment
+            .items()
+            .into_iter()
+            .filter_map(|item| {
+                let (_, entry) = inventory.entries.get(item).ok()?;
+                inventory.items.get(&entry.asset_path)
+            })
+            .flat_map(|item| match &item.item_data {
+                ItemType::Weapon { effects, .. } => effects.clone(),
+                _ => Vec::new(),
+            })
+            .collect();
+
+        // make sure the item exists before the round starts so a typo doesn't cost a turn.
+        let item = if let BattleCmd::Use { item } = cmd {
+            if ailments.hinders(&active, Hindrance::UsingItems) {
+                encounter.log.push("You can't use items right now.".into());
+                continue;
+            }
+
+            let thing = NounPhrase::parse(item);
+
+            if thing.names_nothing() {
+                encounter.log.push("Use what?".into());
+                continue;
+            }
+
+            let carried: Vec<((Entity, &ItemAsset), String)> = inventory
+                .iter()
+                .map(|(entity, _, asset)| ((entity, asset), asset.name.clone()))
+                .collect();
+
+            match pick(&thing, &carried) {
+                Picked::Things(picked) if !picked.is_empty() => {
+                    let (entity, asset) = picked[0];
+
+                    if !matches!(asset.item_data, ItemType::Consumable { .. }) {
+                        encounter
+                            .log
+                            .push(format!("You can't use the {} here.", asset.name));
+                        continue;
+                    }
+
+                    Some((entity, asset.clone()))
+                }
+                Picked::Ambiguous(options) => {
+                    let ask = Disambiguation::new("use", &thing, options);
+                    encounter.log.push(ask.question());
+                    question.0 = Some(ask);
+                    continue;
+                }
+                _ => {
+                    encounter
+                        .log
+                        .push(format!("You don't have a {}.", thing.name));
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        encounter.round += 1;
+        aftermath.turn_ev.write_default();
+        let round = encounter.round;
+        encounter.log.push(format!("-- round {round} --"));
+        // defending happens before anyone moves.
+        encounter.player_defending = *cmd == BattleCmd::Defend;
+        // the mobs ailments change its stats the same way the players change theirs.
+        let mob_stats = apply_layer(
+            &encounter.mob_stats,
+            &ailments.modifiers(&encounter.mob_effects),
+        );
+
+        for turn in turn_order(stats.speed, mob_stats.speed) {
+            if encounter.outcome.is_some() {
+                break;
+            }
+
+            match turn {
+                Turn::Player if ailments.hinders(&active, Hindrance::Acting) => {
+                    encounter.log.push("You can't move!".into());
+                }
+                Turn::Player => match cmd {
+                    BattleCmd::Attack => {
+                        let dealt = damage(stats.attack, mob_stats.defence);
+                        encounter.mob_hp = (encounter.mob_hp - dealt).max(0.0);
+                        let line =
+                            format!("You hit the {} for {dealt:.0} damage.", encounter.mob_name);
+                        encounter.log.push(line);
+
+                        for broke in wear_weapons(&mut cmds, &mut equipment, &inventory) {
+                            encounter.log.push(format!("Your {broke} breaks!"));
+                            notification_ev.write(Notification::new(
+                                NotificationLevel::Alert,
+                                format!("Your {broke} broke."),
+                            ));
+                        }
+
+                        if encounter.mob_hp <= 0.0 {
+                            encounter.outcome = Some(BattleOutcome::Victory);
+                            continue;
+                        }
+
+                        for effect in roll_on_hit("You", &on_hit, &mut rng.0) {
+                            let line =
+                                format!("The {} is afflicted with {effect}.", encounter.mob_name);
+                            encounter.log.push(line);
+                            encounter.mob_effects.add(effect);
+                        }
+                    }
+                    BattleCmd::Defend => encounter.log.push("You brace yourself.".into()),
+                    BattleCmd::Use { .. } => {
+                        if let Some((entity, item)) = &item {
+                            let lines = apply_consumable(
+                                item,
+                                &mut health,
+                                &stats,
+                                &mut active,
+                                &mut rng.0,
+                            );
+                            encounter.log.extend(lines);
+
+                            let repairs = repair_amount(&item.item_data);
+
+                            if repairs > 0 {
+                                let repaired =
+                                    repair_equipped(&mut cmds, &equipment, &inventory, repairs);
+                                encounter.log.extend(
+                                    repaired
+                                        .into_iter()
+                                        .map(|name| format!("Repaired: {name}.")),
+                                );
+                            }
+
+                            if let Ok((_, entry)) = inventory.entries.get(*entity) {
+                                remove_from_stack(&mut cmds, *entity, entry, 1);
+                            }
+                        }
+                    }
+                    BattleCmd::Flee => {
+                        if roll_proc(&mut rng.0, flee_chance(stats.speed, mob_stats.speed)) {
+                            encounter.outcome = Some(BattleOutcome::Fled);
+                        } else {
+                            let line = format!("The {} cuts off your escape!", encounter.mob_name);
+                            encounter.log.push(line);
+                        }
+                    }
+                },
+                Turn::Mob if ailments.hinders(&encounter.mob_effects, Hindrance::Acting) => {
+                    let line = format!("The {} can't move!", encounter.mob_name);
+                    encounter.log.push(line);
+                }
+                Turn::Mob => {
+                    let mut dealt = damage(mob_stats.attack, stats.defence);
+
+                    if encounter.player_defending {
+                        dealt = (dealt / 2.0).floor().max(1.0);
+                    }
+
+                    health.0 = (health.0 - dealt).max(0.0);
+                    let line =
+                        format!("The {} hits you for {dealt:.0} damage.", encounter.mob_name);
+                    encounter.log.push(line);
+
+                    if health.0 <= 0.0 {
+                        encounter.outcome = Some(BattleOutcome::Defeat);
+                        continue;
+                    }
+
+                    let name = encounter.mob_name.clone();
+                    // started on the player, so tick_effects tells them about it.
+                    for effect in roll_on_hit(&name, &encounter.mob_on_hit, &mut rng.0) {
+                        active.add(effect);
+                    }
+                }
+            }
+        }
+
+        if encounter.outcome.is_none() {
+            let max_hp = encounter.mob_stats.hp;
+            let tick = encounter
+                .mob_effects
+                .tick(1.0, max_hp, |ailment| ailments.damage(ailment));
+
+            if tick.damage > 0.0 {
+                encounter.mob_hp = (encounter.mob_hp - tick.damage).max(0.0);
+                let line = format!(
+                    "The {} suffers {:.0} damage from its ailments.",
+                    encounter.mob_name, tick.damage
+                );
+                encounter.log.push(line);
+
+                if encounter.mob_hp <= 0.0 {
+                    encounter.outcome = Some(BattleOutcome::Victory);
+                }
+            }
+
+            for effect in tick.ended {
+                let line = format!("The {} recovers from {effect}.", encounter.mob_name);
+                encounter.log.push(line);
+            }
+        }
+
+        aftermath.settle(&mut encounter, &mut health, stats.hp, &mut rng.0);
+    }
+}
+
+/// draws the battle view in the main text panel.
+fn render_battle(
+    encounter: Res<Encounter>,
+    mut new_zone_evs: MessageReader<NewZone>,
+    player: Query<(&EffectiveStats, &Health, Ref<ActiveEffects>), With<Player>>,
+    mut update_event: MessageWriter<UpdateMainSectionText>,
+) {
+    // NewZone is sent when the main screen is redrawn (ie. when coming back from the inventory).
+    let redraw = new_zone_evs.read().count() > 0;
+
+    let Ok((stats, health, effects)) = player.single() else {
+        return;
+    };
+
+    if !encounter.is_changed() && !effects.is_changed() && !redraw {
+        return;
+    }
+
+    let log_start = encounter.log.len().saturating_sub(LOG_LINES);
+    let log = encounter.log[log_start..].join("\n");
+
+    update_event.write(UpdateMainSectionText(format!(
+        "=== BATTLE: {} ===\n\n{: <12} HP {:.0}/{:.0}{}\n{: <12} HP {:.0}/{:.0}{}\n\n{log}\n\n[attack | defend | use <item> | flee]",
+        encounter.mob_name,
+        encounter.mob_name,
+        encounter.mob_hp,
+        encounter.mob_stats.hp,
+        ailment_tags(&encounter.mob_effects),
+        "You",
+        health.0,
+        stats.0.hp,
+        ailment_tags(&effects),
+    )));
+}
+
+/// cleans up after a fight and tells the player how it went.
+fn end_battle(
+    mut cmds: Commands,
+    encounter: Option<Res<Encounter>>,
+    mut new_zone_ev: MessageWriter<NewZone>,
+    mut look_event: MessageWriter<UpdateLookSectionText>,
+) {
+    if let Some(encounter) = encounter {
+        let summary = match encounter.outcome {
+            Some(BattleOutcome::Victory) if encounter.mob_xp > 0 => format!(
+                "You defeated the {}! You gain {} XP.",
+                encounter.mob_name, encounter.mob_xp
+            ),
+            Some(BattleOutcome::Victory) => format!("You defeated the {}!", encounter.mob_name),
+            Some(BattleOutcome::Defeat) => format!(
+                "The {} knocked you out. You wake up back at the town gate.",
+                encounter.mob_name
+            ),
+            Some(BattleOutcome::Fled) => format!("You got away from the {}.", encounter.mob_name),
+            None => String::new(),
+        };
+        info!(
+            "battle with {} ended: {:?}",
+            encounter.mob_id, encounter.outcome
+        );
+        look_event.write(UpdateLookSectionText(summary));
+    }
+
+    cmds.remove_resource::<Encounter>();
+    new_zone_ev.write_default();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Turn, damage, flee_chance, turn_order};
+    use crate::ailments::{ProcRng, roll_proc};
+
+    #[test]
+    fn damage_is_softened_by_defence() {
+        assert_eq!(damage(6.0, 0.0), 6.0);
+        assert!(damage(6.0, 6.0) < damage(6.0, 0.0));
+        assert_eq!(
+            damage(1.0, 1000.0),
+            1.0,
+            "a hit should always do some damage"
+        );
+        assert_eq!(damage(0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn faster_combatant_goes_first() {
+        assert_eq!(turn_order(3.0, 2.5), [Turn::Player, Turn::Mob]);
+        assert_eq!(turn_order(2.0, 2.5), [Turn::Mob, Turn::Player]);
+        assert_eq!(turn_order(2.5, 2.5), [Turn::Player, Turn::Mob]);
+    }
+
+    #[test]
+    fn fleeing_is_weighted_by_speed() {
+        assert_eq!(flee_chance(2.0, 2.0), 0.5);
+        assert_eq!(flee_chance(0.0, 0.0), 0.5);
+        assert!(flee_chance(3.0, 1.0) > flee_chance(1.0, 3.0));
+
+        // a faster mob makes it harder, not impossible.
+        let mut rng = ProcRng::seeded(7);
+        let escapes = (0..1000)
+            .filter(|_| roll_proc(&mut rng.0, flee_chance(1.0, 3.0)))
+            .count();
+        assert!((150..350).contains(&escapes), "got away {escapes} times");
+    }
+}
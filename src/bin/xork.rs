@@ -9,21 +9,47 @@ use bevy_common_assets::ron::RonAssetPlugin;
 use bevy_simple_text_input::TextInputPlugin;
 use std::{error::Error, fs::read_dir, path::PathBuf};
 use xork::{
-    ChangeScreen, CommandEntered, CommandResultEvent, ExitGame, NewZone, Notification, PlayerLook,
-    PlayerMovement, PlayerTake, UiMessage, WindowSize,
+    Autosave, ChangeScreen, CommandEntered, CommandResultEvent, ExitGame, LoadGame, NewZone,
+    PlayerDrop, PlayerEquip, PlayerExamine, PlayerInventory, PlayerLook, PlayerMovement,
+    PlayerTake, PlayerUnequip, PlayerUse, SaveGame, TurnTaken, UiMessage, WindowSize,
+    ailments::{AilmentAsset, Ailments, ProcRng},
+    battle::BattlePlugin,
+    carry::update_load_modifiers,
     commands::commands::SlashCmd,
-    enter_exit_state, enter_in_game_state, exit_game,
+    effects::{EffectEnded, EffectStarted, TimeSource, report_effects, tick_effects},
+    encounters::{EncounterRng, roll_for_encounter},
+    enter_exit_state, enter_in_game_state,
+    equipment::update_equipment_modifiers,
+    exit_game,
+    flavor::{FlavorAsset, FlavorTexts, RecentFlavor},
+    handle_bad_command::handle_bad_command,
     handle_exit_command::slash_exit,
     handle_game_cmd::handle_game_cmd,
+    handle_player_drop::handle_player_drop,
+    handle_player_equip::{handle_player_equip, handle_player_unequip},
+    handle_player_examine::handle_player_examine,
+    handle_player_inventory::handle_player_inventory,
     handle_player_look::handle_player_look,
     handle_player_move::{handle_player_movement, send_new_zone},
+    handle_player_use::handle_player_use,
     handle_slash_cmd::slash_cmd,
     items::{ItemAsset, Items},
+    leveling::{LevelCurveAsset, LevelingPlugin},
     maintain_window_size,
     mobs::{MobAsset, Mobs},
+    notifications::NotificationsPlugin,
+    player::{SPAWN_ZONE, spawn_player},
     player_take::handle_player_take,
+    save::{
+        AutosaveConfig, autosave_on_wrapup, handle_autosave, handle_load_game, handle_save_game,
+        offer_resume,
+    },
+    shops::{ShopAsset, Shops, ShopsPlugin},
     state::{GameState, InventoryState, MainScreenState, MainState},
+    stats::update_effective_stats,
     ui::TextUiPlugin,
+    wallet::WalletPlugin,
+    world::WorldState,
     zones::{Location, ZoneAsset, Zones},
 };
 
@@ -61,16 +87,36 @@ fn main() -> Result<(), Box<dyn Error>> {
             RonAssetPlugin::<ZoneAsset>::new(&["zone.ron"]),
             RonAssetPlugin::<MobAsset>::new(&["mob.ron"]),
             RonAssetPlugin::<ItemAsset>::new(&["item.ron"]),
+            RonAssetPlugin::<AilmentAsset>::new(&["ailment.ron"]),
+            RonAssetPlugin::<FlavorAsset>::new(&["flavor.ron"]),
+            RonAssetPlugin::<ShopAsset>::new(&["shop.ron"]),
+            RonAssetPlugin::<LevelCurveAsset>::new(&["levels.ron"]),
             TextUiPlugin,
             WireframePlugin::default(),
             TextInputPlugin,
             // MenuScreensPlugin,
             // Wireframe2dPlugin,
         ))
+        .add_plugins((
+            BattlePlugin,
+            NotificationsPlugin,
+            ShopsPlugin,
+            WalletPlugin,
+            LevelingPlugin,
+        ))
         .insert_resource(Zones::default())
         .insert_resource(Mobs::default())
         .insert_resource(Items::default())
-        .insert_resource(Location("starter-town/gate.zone.ron".into()))
+        .insert_resource(Ailments::default())
+        .insert_resource(FlavorTexts::default())
+        .insert_resource(Shops::default())
+        .init_resource::<RecentFlavor>()
+        .insert_resource(Location(SPAWN_ZONE.into()))
+        .init_resource::<AutosaveConfig>()
+        .init_resource::<EncounterRng>()
+        .init_resource::<ProcRng>()
+        .init_resource::<WorldState>()
+        .init_resource::<TimeSource>()
         // .configure_sets(Update, Adventure.run_if(in_state(GameState::Adventure)))
         // .configure_sets(Update, InGame.run_if(not(in_state(GameState::Startup))))
         // .init_resource::<Zone>()
@@ -92,18 +138,37 @@ fn main() -> Result<(), Box<dyn Error>> {
         .add_message::<UiMessage>()
         .add_message::<PlayerMovement>()
         .add_message::<PlayerLook>()
-        .add_message::<Notification>()
         .add_message::<CommandResultEvent>()
         .add_message::<ExitGame>()
         .add_message::<SlashCmd>()
         .add_message::<NewZone>()
         .add_message::<PlayerTake>()
+        .add_message::<PlayerDrop>()
+        .add_message::<PlayerInventory>()
+        .add_message::<PlayerExamine>()
+        .add_message::<PlayerUse>()
+        .add_message::<PlayerEquip>()
+        .add_message::<PlayerUnequip>()
+        .add_message::<TurnTaken>()
         .add_message::<ChangeScreen>()
+        .add_message::<SaveGame>()
+        .add_message::<LoadGame>()
+        .add_message::<Autosave>()
+        .add_message::<EffectStarted>()
+        .add_message::<EffectEnded>()
         .init_asset::<ZoneAsset>()
         .init_asset::<MobAsset>()
         .add_systems(
             OnEnter(MainState::InGame),
-            (load_zone_assets, load_mob_assets, load_item_assets),
+            (
+                load_zone_assets,
+                load_mob_assets,
+                load_item_assets,
+                load_ailment_assets,
+                load_flavor_assets,
+                load_shop_assets,
+                spawn_player,
+            ),
         )
         .add_systems(
             Startup,
@@ -114,14 +179,46 @@ fn main() -> Result<(), Box<dyn Error>> {
             (
                 handle_game_cmd,
                 slash_cmd,
-                handle_player_movement,
+                handle_player_movement.after(handle_game_cmd),
+                roll_for_encounter.after(handle_player_movement),
                 handle_player_look,
                 handle_player_take,
+                handle_player_drop,
+                handle_player_inventory,
+                handle_player_examine,
+                handle_player_use,
+                handle_player_equip,
+                handle_player_unequip,
+                update_equipment_modifiers,
+                tick_effects.after(handle_game_cmd),
+                report_effects.after(tick_effects),
+                update_effective_stats
+                    .after(update_equipment_modifiers)
+                    .after(tick_effects),
+                handle_save_game,
+                handle_load_game,
+                handle_autosave,
                 slash_exit,
             )
                 .run_if(in_state(MainState::InGame)),
         )
+        .add_systems(
+            Update,
+            (
+                handle_bad_command,
+                update_load_modifiers.before(update_effective_stats),
+            )
+                .run_if(in_state(MainState::InGame)),
+        )
         .add_systems(Update, send_new_zone.run_if(in_state(GameState::Startup)))
+        .add_systems(
+            OnTransition {
+                exited: GameState::Startup,
+                entered: GameState::Adventure,
+            },
+            offer_resume,
+        )
+        .add_systems(OnEnter(MainState::Wrapup), autosave_on_wrapup)
         .add_systems(Update, enter_exit_state.run_if(in_state(MainState::Wrapup)))
         .add_systems(Update, maintain_window_size)
         .add_systems(OnEnter(MainState::Exit), exit_game)
@@ -183,6 +280,42 @@ fn load_item_assets(mut items: ResMut<Items>, asset_server: Res<AssetServer>) {
     });
 }
 
+fn load_ailment_assets(mut ailments: ResMut<Ailments>, asset_server: Res<AssetServer>) {
+    let to_assets = PathBuf::from("assets/ailments");
+
+    read_dir(to_assets).unwrap().for_each(|asset| {
+        if let Ok(asset) = asset {
+            let path = format!("ailments/{}", asset.file_name().to_str().unwrap());
+            info!("{path}");
+            ailments.0.insert(path.clone(), asset_server.load(path));
+        }
+    });
+}
+
+fn load_flavor_assets(mut flavor: ResMut<FlavorTexts>, asset_server: Res<AssetServer>) {
+    let to_assets = PathBuf::from("assets/flavor");
+
+    read_dir(to_assets).unwrap().for_each(|asset| {
+        if let Ok(asset) = asset {
+            let path = format!("flavor/{}", asset.file_name().to_str().unwrap());
+            info!("{path}");
+            flavor.0.insert(path.clone(), asset_server.load(path));
+        }
+    });
+}
+
+fn load_shop_assets(mut shops: ResMut<Shops>, asset_server: Res<AssetServer>) {
+    let to_assets = PathBuf::from("assets/shops");
+
+    read_dir(to_assets).unwrap().for_each(|asset| {
+        if let Ok(asset) = asset {
+            let path = format!("shops/{}", asset.file_name().to_str().unwrap());
+            info!("{path}");
+            shops.0.insert(path.clone(), asset_server.load(path));
+        }
+    });
+}
+
 // fn ui_system(mut ctx: IcedContext<UiMessage>, notifs: Query<&Notification>) {
 //     // ctx.display(text(format!(
 //     //     "Hello Iced! Running for {:.2} seconds.",
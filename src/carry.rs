@@ -0,0 +1,88 @@
+use crate::{
+    items::{Amount, InventoryEntry, ItemAsset, Items, Stat},
+    player::{CombatStats, EffectiveStats, Player},
+    stats::{StatModifier, StatModifiers},
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// past this much of their capacity the player starts to slow down.
+pub const HEAVY_LOAD: f32 = 0.75;
+
+/// how much the player can carry. stronger & tougher players carry more.
+pub fn carry_capacity(stats: &CombatStats) -> f32 {
+    10.0 + stats.attack * 2.0 + stats.hp * 0.25
+}
+
+/// how much a load slows the player down. nothing under `HEAVY_LOAD`, a quarter of their speed
+/// when heavily loaded, and half when they're carrying more than they should (ie. after a strength
+/// buff wore off).
+pub fn load_penalty(weight: f32, capacity: f32) -> Option<StatModifier> {
+    let amount = if weight > capacity {
+        -0.5
+    } else if weight > capacity * HEAVY_LOAD {
+        -0.25
+    } else {
+        return None;
+    };
+
+    Some(StatModifier {
+        stat: Stat::Speed,
+        amount: Amount::Percent(amount),
+    })
+}
+
+/// what the player is carrying compared to what they can carry.
+#[derive(SystemParam)]
+pub struct CarryLoad<'w, 's> {
+    inventory: Query<'w, 's, &'static InventoryEntry>,
+    player: Query<'w, 's, &'static EffectiveStats, With<Player>>,
+    item_assets: Res<'w, Assets<ItemAsset>>,
+    items: Res<'w, Items>,
+}
+
+impl CarryLoad<'_, '_> {
+    /// the weight of everything in the inventory.
+    pub fn weight(&self) -> f32 {
+        self.inventory
+            .iter()
+            .filter_map(|entry| {
+                let asset = self.item_assets.get(self.items.0.get(&entry.asset_path)?)?;
+                Some(asset.weight * entry.count as f32)
+            })
+            .sum()
+    }
+
+    pub fn capacity(&self) -> f32 {
+        self.player
+            .single()
+            .map(|stats| carry_capacity(&stats.0))
+            .unwrap_or_default()
+    }
+}
+
+/// slows the player down when they're carrying a heavy load.
+pub fn update_load_modifiers(load: CarryLoad, mut player: Query<&mut StatModifiers, With<Player>>) {
+    let Ok(mut modifiers) = player.single_mut() else {
+        return;
+    };
+
+    let penalty: Vec<StatModifier> = load_penalty(load.weight(), load.capacity())
+        .into_iter()
+        .collect();
+
+    if modifiers.load != penalty {
+        modifiers.load = penalty;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::load_penalty;
+
+    #[test]
+    fn heavy_loads_slow_you_down() {
+        assert!(load_penalty(10.0, 30.0).is_none());
+        assert!(load_penalty(25.0, 30.0).is_some());
+        assert_ne!(load_penalty(25.0, 30.0), load_penalty(35.0, 30.0));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::state::InventoryState;
+use crate::{effects::TimeSource, state::InventoryState};
 use bevy::prelude::*;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumDiscriminants, EnumString};
 
@@ -39,6 +39,24 @@ pub enum Direction {
     In { place: String },
 }
 
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::North => write!(f, "north"),
+            Self::South => write!(f, "south"),
+            Self::East => write!(f, "east"),
+            Self::West => write!(f, "west"),
+            Self::Up => write!(f, "up"),
+            Self::Down => write!(f, "down"),
+            Self::NorthEast => write!(f, "north-east"),
+            Self::NorthWest => write!(f, "north-west"),
+            Self::SouthEast => write!(f, "south-east"),
+            Self::SouthWest => write!(f, "south-west"),
+            Self::In { place } => write!(f, "in {place}"),
+        }
+    }
+}
+
 // #[derive(Parser)]
 // #[command(version, about, long_about = None)]
 // struct GameCommand {
@@ -72,6 +90,14 @@ pub enum ViewScreen {
     Notifications,
 }
 
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Toggle {
+    On,
+    Off,
+}
+
 #[derive(
     Debug,
     Clone,
@@ -99,35 +125,187 @@ pub enum ViewScreen {
 pub enum GameCmd {
     // #[serde(rename = "help")]
     // Help { query: Option<Box<ClientGameCmd>> },
+    /// walk to a neighbouring zone
     #[strum_discriminants(serde(rename = "go", alias = "move", alias = "walk", alias = "g"))]
     #[strum_discriminants(clap(alias = "move", alias = "walk", alias = "g"))]
+    #[clap(
+        alias = "move",
+        alias = "walk",
+        alias = "g",
+        after_help = "go north\ngo ne\ngo in tavern"
+    )]
     Go {
         #[command(subcommand)]
         direction: Direction,
     },
-    #[strum_discriminants(serde(
-        rename = "look",
-        alias = "examine",
-        alias = "observe",
-        alias = "behold"
-    ))]
-    #[strum_discriminants(clap(alias = "examine", alias = "observe", alias = "behold"))]
+    /// take a closer look at the zone you are in
+    #[strum_discriminants(serde(rename = "look", alias = "observe", alias = "behold"))]
+    #[strum_discriminants(clap(alias = "observe", alias = "behold"))]
+    #[clap(alias = "observe", alias = "behold")]
     Look,
+    /// pick up items from the ground
     #[strum_discriminants(serde(rename = "take", alias = "pick-up", alias = "yoink"))]
     #[strum_discriminants(clap(alias = "pick-up", alias = "yoink"))]
+    #[clap(
+        alias = "pick-up",
+        alias = "yoink",
+        after_help = "take club\ntake 2 potions\ntake all"
+    )]
     Take {
-        // #[arg(action = ArgAction::Append, required = true, value_delimiter = ' ', num_args = 1.., use_value_delimiter = true)]
-        // thing: Vec<String>,
+        /// what to take, ie. "club", "3 clubs" or "all".
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
+    },
+    /// leave items from the inventory on the ground
+    #[strum_discriminants(serde(rename = "drop", alias = "discard", alias = "toss"))]
+    #[strum_discriminants(clap(alias = "discard", alias = "toss"))]
+    #[clap(
+        alias = "discard",
+        alias = "toss",
+        after_help = "drop club\ndrop all potions"
+    )]
+    Drop {
+        /// what to drop, ie. "club", "3 clubs" or "all".
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
+    },
+    /// lists item in the inventory
+    #[strum_discriminants(serde(
+        rename = "inventory",
+        alias = "inv",
+        alias = "list",
+        alias = "ls",
+        alias = "i"
+    ))]
+    #[strum_discriminants(clap(alias = "inv", alias = "list", alias = "ls", alias = "i"))]
+    #[clap(alias = "inv", alias = "list", alias = "ls", alias = "i")]
+    Inventory {},
+    /// take a closer look at an item in the inventory or on the ground
+    #[strum_discriminants(serde(rename = "examine", alias = "inspect", alias = "x"))]
+    #[strum_discriminants(clap(alias = "inspect", alias = "x"))]
+    #[clap(
+        alias = "inspect",
+        alias = "x",
+        after_help = "examine club\nx health potion"
+    )]
+    Examine {
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
+    },
+    /// use a consumable or key item from the inventory
+    #[strum_discriminants(serde(
+        rename = "use",
+        alias = "consume",
+        alias = "drink",
+        alias = "eat"
+    ))]
+    #[strum_discriminants(clap(alias = "consume", alias = "drink", alias = "eat"))]
+    #[clap(
+        alias = "consume",
+        alias = "drink",
+        alias = "eat",
+        after_help = "use health potion\ndrink antidote"
+    )]
+    Use {
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
+    },
+    /// hold a weapon or put on armor
+    #[strum_discriminants(serde(rename = "equip", alias = "wield", alias = "wear"))]
+    #[strum_discriminants(clap(alias = "wield", alias = "wear"))]
+    #[clap(
+        alias = "wield",
+        alias = "wear",
+        after_help = "equip club\nwield goblin club"
+    )]
+    Equip {
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
+    },
+    /// put away a weapon or take off armor
+    #[strum_discriminants(serde(rename = "unequip", alias = "unwield", alias = "remove"))]
+    #[strum_discriminants(clap(alias = "unwield", alias = "remove"))]
+    #[clap(alias = "unwield", alias = "remove", after_help = "unequip club")]
+    Unequip {
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
     },
-    // /// lists item in the inventory
-    // #[serde(rename = "inventory", alias = "list", alias = "ls")]
-    // #[clap(alias = "inv", alias = "list", alias = "ls")]
-    // Inventory {},
     // TODO: make a "map" command that allow the player to check a mini map.
 }
 
+/// commands that can only be used while in a battle.
 #[derive(Debug, Clone, Serialize, Deserialize, Message, Parser, PartialEq, Eq, PartialOrd, Ord)]
 #[command(version, about, no_binary_name(true), long_about = None)]
+pub enum BattleCmd {
+    /// hit the enemy with what ever you're holding
+    #[clap(alias = "a", alias = "hit", alias = "fight")]
+    Attack,
+    /// brace for the next hit, halving the damage it does
+    #[clap(alias = "d", alias = "block", alias = "guard")]
+    Defend,
+    /// use a consumable from your inventory
+    #[clap(alias = "u")]
+    Use {
+        #[arg(required = true, num_args = 1..)]
+        item: Vec<String>,
+    },
+    /// try to run away
+    #[clap(alias = "f", alias = "run", alias = "escape")]
+    Flee,
+}
+
+/// commands that can only be used while in a shop.
+#[derive(Debug, Clone, Serialize, Deserialize, Message, Parser, PartialEq, Eq, PartialOrd, Ord)]
+#[command(version, about, no_binary_name(true), long_about = None)]
+pub enum ShopCmd {
+    /// buy something the shop sells
+    #[clap(
+        alias = "b",
+        alias = "purchase",
+        after_help = "buy health potion\nbuy 3 antidotes"
+    )]
+    Buy {
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
+    },
+    /// sell something from your inventory
+    #[clap(alias = "s", after_help = "sell club\nsell all clubs")]
+    Sell {
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        thing: Vec<String>,
+    },
+    /// list what the shop sells & what it would pay for your things
+    #[clap(alias = "ls", alias = "l")]
+    List,
+    /// walk back out of the shop
+    #[clap(alias = "exit", alias = "out", alias = "bye")]
+    Leave,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    Message,
+    Parser,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumDiscriminants,
+)]
+#[strum_discriminants(derive(
+    EnumString,
+    Display,
+    Serialize,
+    Deserialize,
+    PartialOrd,
+    Ord,
+    Parser
+))]
+#[strum_discriminants(name(SlashCmdName))]
+#[command(version, about, no_binary_name(true), long_about = None)]
 pub enum SlashCmd {
     // #[clap(name = "/say", alias = "/speak", alias = "/chat")]
     // Say {
@@ -140,30 +318,93 @@ pub enum SlashCmd {
     //     #[arg(action = ArgAction::Append, required = true)]
     //     message: Vec<String>,
     // },
-    #[clap(name = "/help", alias = "/?", alias = "/h")]
+    /// list every command, or explain how to use one
+    #[strum_discriminants(clap(name = "/help", alias = "/?", alias = "/h"))]
+    #[clap(
+        name = "/help",
+        alias = "/?",
+        alias = "/h",
+        disable_help_subcommand = true,
+        after_help = "/help\n/help go\n/help /save"
+    )]
     Help {
-        // #[arg(required = true)]
         #[command(subcommand)]
-        with: GameCmdName,
+        with: Option<HelpTopic>,
     },
-    #[clap(name = "/save")]
+    /// save the game to a numbered slot
+    #[strum_discriminants(clap(name = "/save"))]
+    #[clap(name = "/save", after_help = "/save 1")]
     Save {
         #[arg(required = true)]
         // #[command(subcommand)]
         save_slot: u8,
     },
+    /// load the game from a numbered slot
+    #[strum_discriminants(clap(name = "/load"))]
+    #[clap(name = "/load", after_help = "/load 1")]
+    Load {
+        #[arg(required = true)]
+        save_slot: u8,
+    },
+    /// load the newest autosave
+    #[strum_discriminants(clap(name = "/resume", alias = "/continue"))]
+    #[clap(name = "/resume", alias = "/continue")]
+    Resume {},
+    /// turn autosaving on every zone change on or off
+    #[strum_discriminants(clap(name = "/autosave"))]
+    #[clap(name = "/autosave", after_help = "/autosave on\n/autosave off")]
+    Autosave {
+        #[arg(required = true)]
+        on_zone_change: Toggle,
+    },
+    /// make timed effects run out in real time or turn by turn
+    #[strum_discriminants(clap(name = "/time"))]
+    #[clap(name = "/time", after_help = "/time turns\n/time real-time")]
+    Time {
+        #[arg(required = true)]
+        source: TimeSource,
+    },
+    /// save & quit the game
+    #[strum_discriminants(clap(name = "/exit", alias = "/e", alias = "/quit", alias = "/q"))]
     #[clap(name = "/exit", alias = "/e", alias = "/quit", alias = "/q")]
     Exit {},
-    #[clap(name = "/view", alias = "/v")]
+    /// switch to another screen, ie. the inventory or your stats
+    #[strum_discriminants(clap(name = "/view", alias = "/v"))]
+    #[clap(
+        name = "/view",
+        alias = "/v",
+        after_help = "/view inventory\n/view inv equipment\n/view stats\n/view game"
+    )]
     View {
         #[command(subcommand)]
         screen: ViewScreen,
     },
 }
 
+/// what `/help` can explain, either a game command or a slash command.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, Subcommand, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum HelpTopic {
+    #[command(flatten)]
+    Game(GameCmdName),
+    #[command(flatten)]
+    Slash(SlashCmdName),
+}
+
+impl HelpTopic {
+    /// the name the command is typed as, ie. "go" or "/save".
+    pub fn name(&self) -> String {
+        match self {
+            Self::Game(name) => name.to_string().to_lowercase(),
+            Self::Slash(name) => format!("/{}", name.to_string().to_lowercase()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{Direction, GameCmd, GameCmdName, SlashCmd};
+    use super::{Direction, GameCmd, GameCmdName, HelpTopic, SlashCmd, SlashCmdName};
     use clap::Parser;
 
     #[test]
@@ -173,10 +414,16 @@ mod test {
         // let cmd = ClientGameCmd::parse_from(["go", "north"]);
         let cmd = GameCmd::try_parse_from(["take", "nut", "cracker", "9000"]);
         // println!("{:?}", cmd.cmd);
-        if cmd.is_ok() {
+        if cmd.is_err() {
             println!("{:?}", cmd);
         }
-        assert!(cmd.is_err(), "can not YET take specififc items");
+        assert!(
+            cmd.is_ok_and(|parsed| parsed
+                == GameCmd::Take {
+                    thing: vec!["nut".into(), "cracker".into(), "9000".into()]
+                }),
+            "take should accept the name of the item to take"
+        );
 
         let cmd = GameCmd::try_parse_from(["go", "n"]);
         // println!("{:?}", cmd.cmd);
@@ -193,6 +440,29 @@ mod test {
             Direction::North
         );
 
+        assert_eq!(
+            GameCmd::try_parse_from(["x", "goblin", "club"]).ok(),
+            Some(GameCmd::Examine {
+                thing: vec!["goblin".into(), "club".into()]
+            }),
+            "examine is its own verb, not an alias of look"
+        );
+        assert_eq!(
+            GameCmd::try_parse_from(["walk", "n"]).ok(),
+            Some(GameCmd::Go {
+                direction: Direction::North
+            }),
+            "the aliases listed in /help should parse too"
+        );
+        assert_eq!(
+            GameCmd::try_parse_from(["inv"]).ok(),
+            Some(GameCmd::Inventory {})
+        );
+        assert!(
+            GameCmd::try_parse_from(["equip"]).is_err(),
+            "equip needs to know what to equip"
+        );
+
         let cmd = GameCmd::try_parse_from(["foo", "bar"]);
         // println!("{:?}", cmd.cmd);
         if cmd.is_ok() {
@@ -273,7 +543,7 @@ mod test {
         assert_eq!(
             cmd,
             SlashCmd::Help {
-                with: GameCmdName::Go
+                with: Some(HelpTopic::Game(GameCmdName::Go))
             },
             "expected a \"/help\" command, got: {cmd:?}",
         );
@@ -286,9 +556,21 @@ mod test {
         assert_eq!(
             cmd,
             SlashCmd::Help {
-                with: GameCmdName::Go
+                with: Some(HelpTopic::Game(GameCmdName::Go))
             },
             "expected a \"/help\" command, got: {cmd:?}",
         );
+        assert_eq!(
+            SlashCmd::try_parse_from(["/help"]).ok(),
+            Some(SlashCmd::Help { with: None }),
+            "/help on its own lists every command"
+        );
+        assert_eq!(
+            SlashCmd::try_parse_from(["/?", "/q"]).ok(),
+            Some(SlashCmd::Help {
+                with: Some(HelpTopic::Slash(SlashCmdName::Exit))
+            }),
+            "slash commands have help too"
+        );
     }
 }
@@ -1,7 +1,27 @@
 use bevy::prelude::*;
-use serde::{Deserialize, Serialize};
+use clap::error::ErrorKind;
 
+#[allow(clippy::module_inception)]
 pub mod commands;
+pub mod suggest;
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, Message, PartialEq, Eq, PartialOrd, Ord)]
-pub struct BadCommand;
+/// input that didn't parse as a command.
+#[derive(Debug, Clone, Message, PartialEq, Eq)]
+pub struct BadCommand {
+    /// what the player typed.
+    pub input: String,
+    /// what kind of mistake clap found.
+    pub kind: ErrorKind,
+    /// clap's description of the mistake.
+    pub error: String,
+}
+
+impl BadCommand {
+    pub fn new(input: impl Into<String>, error: &clap::Error) -> Self {
+        Self {
+            input: input.into(),
+            kind: error.kind(),
+            error: error.to_string(),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use clap::Command;
+
+/// the edit distance between two words, counting swapped neighbouring letters as one edit. "og"
+/// is one edit away from "go".
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    rows[0] = (0..=b.len()).collect();
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// the closest name (or alias) of a sub command to `word`, if any are close enough to be a typo.
+fn closest<'a>(cmd: &'a Command, word: &str) -> Option<(&'a str, &'a Command)> {
+    // short words need to be nearly right, otherwise everything is a typo of "go".
+    let max_distance = if word.chars().count() <= 3 { 1 } else { 2 };
+
+    cmd.get_subcommands()
+        .filter(|sub| sub.get_name() != "help")
+        .flat_map(|sub| {
+            std::iter::once(sub.get_name())
+                .chain(sub.get_all_aliases())
+                .map(move |name| (name, sub))
+        })
+        .map(|(name, sub)| (edit_distance(word, name), name, sub))
+        .filter(|(distance, name, _)| *distance <= max_distance && *distance < name.len())
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, name, sub)| (name, sub))
+}
+
+/// guesses what the player meant to type by fixing misspelled command (and sub command) names.
+/// None if nothing could be fixed, or nothing needed fixing.
+pub fn suggest(root: &Command, input: &str) -> Option<String> {
+    let mut cmd = root;
+    let mut fixed = false;
+    let mut words = Vec::new();
+
+    for word in input.split_whitespace() {
+        let lower = word.to_lowercase();
+
+        // past the last sub command, the rest are arguments (ie. item names).
+        if cmd.get_subcommands().all(|sub| sub.get_name() == "help") {
+            words.push(word.to_string());
+            continue;
+        }
+
+        if let Some(sub) = cmd.find_subcommand(&lower) {
+            words.push(lower);
+            cmd = sub;
+        } else {
+            let (name, sub) = closest(cmd, &lower)?;
+            words.push(name.to_string());
+            cmd = sub;
+            fixed = true;
+        }
+    }
+
+    fixed.then(|| words.join(" "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{edit_distance, suggest};
+    use crate::commands::commands::{GameCmd, SlashCmd};
+    use clap::CommandFactory;
+
+    #[test]
+    fn typos_are_a_few_edits_away() {
+        assert_eq!(edit_distance("go", "go"), 0);
+        assert_eq!(edit_distance("og", "go"), 1);
+        assert_eq!(edit_distance("nroth", "north"), 1);
+        assert_eq!(edit_distance("tkae", "take"), 1);
+        assert_eq!(edit_distance("", "look"), 4);
+    }
+
+    #[test]
+    fn suggestions_fix_commands_and_sub_commands() {
+        let game = GameCmd::command();
+
+        assert_eq!(suggest(&game, "og north"), Some("go north".into()));
+        assert_eq!(suggest(&game, "go nroth"), Some("go north".into()));
+        assert_eq!(
+            suggest(&game, "tkae goblin club"),
+            Some("take goblin club".into()),
+            "item names are left alone"
+        );
+        assert_eq!(suggest(&game, "go north"), None, "nothing to fix");
+        assert_eq!(suggest(&game, "dance wildly"), None, "nothing close");
+
+        let slash = SlashCmd::command();
+        assert_eq!(suggest(&slash, "/sav 1"), Some("/save 1".into()));
+        assert_eq!(
+            suggest(&slash, "/view invnetory"),
+            Some("/view inventory".into())
+        );
+    }
+}
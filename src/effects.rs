@@ -0,0 +1,437 @@
+use crate::{
+    Notification, NotificationLevel, TurnTaken,
+    ailments::{AilmentData, StatusAilment},
+    items::{Amount, Stat},
+    player::{EffectiveStats, Health, Player},
+    state::GameState,
+    stats::{StatModifier, StatModifiers},
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// what moves timed effects along. can be switched with `/time`, effects that are already running
+/// keep their remaining duration in the new unit.
+#[derive(
+    Resource,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    ValueEnum,
+)]
+pub enum TimeSource {
+    /// durations are in seconds.
+    #[default]
+    RealTime,
+    /// durations are in turns, one for every command that takes a turn.
+    Turns,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EffectKind {
+    /// heals `amount` in total, spread evenly over the duration.
+    HealOverTime { amount: Amount },
+    /// changes a stat until the effect runs out.
+    Buf { stat: Stat, amount: Amount },
+    /// what the ailment does is looked up in its `AilmentAsset`.
+    Ailment { ailment: StatusAilment },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    /// the name of whatever caused the effect, ie. "Health Potion".
+    pub source: String,
+    pub kind: EffectKind,
+    pub duration: f32,
+    pub remaining: f32,
+    /// set once the player has been told the effect started.
+    #[serde(default)]
+    pub announced: bool,
+}
+
+impl ActiveEffect {
+    pub fn new(source: impl Into<String>, kind: EffectKind, duration: f32) -> Self {
+        Self {
+            source: source.into(),
+            kind,
+            duration,
+            remaining: duration,
+            announced: false,
+        }
+    }
+
+    /// true if `other` is the same effect from the same source, ie. a second potion of the same
+    /// kind. an ailment is the same ailment no matter where it came from.
+    fn same_as(&self, other: &Self) -> bool {
+        match (&self.kind, &other.kind) {
+            (EffectKind::HealOverTime { .. }, EffectKind::HealOverTime { .. }) => {
+                self.source == other.source
+            }
+            (
+                EffectKind::Buf { stat, .. },
+                EffectKind::Buf {
+                    stat: other_stat, ..
+                },
+            ) => self.source == other.source && stat == other_stat,
+            (
+                EffectKind::Ailment { ailment },
+                EffectKind::Ailment {
+                    ailment: other_ailment,
+                },
+            ) => ailment == other_ailment,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ActiveEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            EffectKind::HealOverTime { .. } => write!(f, "{} (healing)", self.source),
+            EffectKind::Buf {
+                stat,
+                amount: Amount::Fixed(value),
+            } => write!(f, "{} ({value:+} {stat:?})", self.source),
+            EffectKind::Buf {
+                stat,
+                amount: Amount::Percent(value),
+            } => write!(f, "{} ({:+.0}% {stat:?})", self.source, value * 100.0),
+            EffectKind::Ailment { ailment } => write!(f, "{ailment}"),
+        }
+    }
+}
+
+/// what happened during one tick of the active effects.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EffectsTick {
+    /// how much HP to restore.
+    pub healed: f32,
+    /// how much HP ailments took.
+    pub damage: f32,
+    pub ended: Vec<ActiveEffect>,
+}
+
+/// the timed effects currently on the player.
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActiveEffects(pub Vec<ActiveEffect>);
+
+impl ActiveEffects {
+    /// starts an effect. heals stack, so two potions heal twice as much. buffs from the same
+    /// source & ailments don't stack, instead the running one is refreshed to the longer of the two
+    /// durations.
+    pub fn add(&mut self, effect: ActiveEffect) {
+        if let EffectKind::Buf { .. } | EffectKind::Ailment { .. } = effect.kind
+            && let Some(running) = self.0.iter_mut().find(|running| running.same_as(&effect))
+        {
+            running.duration = running.duration.max(effect.duration);
+            running.remaining = running.remaining.max(effect.remaining);
+            running.announced = false;
+            return;
+        }
+
+        self.0.push(effect);
+    }
+
+    /// moves every effect along by `elapsed` seconds (or turns). `max_hp` is used for heals & damage
+    /// that are a percentage of the afflicted's HP. `damage_of` looks up how much HP an ailment
+    /// takes per second (or turn).
+    pub fn tick(
+        &mut self,
+        elapsed: f32,
+        max_hp: f32,
+        damage_of: impl Fn(StatusAilment) -> Option<Amount>,
+    ) -> EffectsTick {
+        let mut tick = EffectsTick::default();
+
+        if elapsed <= 0.0 {
+            return tick;
+        }
+
+        for effect in self.0.iter_mut() {
+            let step = elapsed.min(effect.remaining);
+
+            if let EffectKind::HealOverTime { amount } = &effect.kind
+                && effect.duration > 0.0
+            {
+                let total = match amount {
+                    Amount::Fixed(hp) => *hp as f32,
+                    Amount::Percent(percent) => max_hp * percent,
+                };
+                tick.healed += total * step / effect.duration;
+            }
+
+            if let EffectKind::Ailment { ailment } = &effect.kind
+                && let Some(damage) = damage_of(*ailment)
+            {
+                let per_tick = match damage {
+                    Amount::Fixed(hp) => hp as f32,
+                    Amount::Percent(percent) => max_hp * percent,
+                };
+                tick.damage += per_tick * step;
+            }
+
+            effect.remaining -= step;
+        }
+
+        let (ended, running) = self.0.drain(..).partition(|effect| effect.remaining <= 0.0);
+        self.0 = running;
+        tick.ended = ended;
+
+        tick
+    }
+
+    pub fn ailments(&self) -> impl Iterator<Item = StatusAilment> + '_ {
+        self.0.iter().filter_map(|effect| match effect.kind {
+            EffectKind::Ailment { ailment } => Some(ailment),
+            _ => None,
+        })
+    }
+
+    /// removes the given ailments, or every ailment if `ailments` is empty. returns the ones that
+    /// were cured.
+    pub fn cure(&mut self, ailments: &[StatusAilment]) -> Vec<ActiveEffect> {
+        let (cured, kept) = self.0.drain(..).partition(|effect| match effect.kind {
+            EffectKind::Ailment { ailment } => ailments.is_empty() || ailments.contains(&ailment),
+            _ => false,
+        });
+        self.0 = kept;
+
+        cured
+    }
+
+    /// the stat modifiers of every running buff.
+    pub fn modifiers(&self) -> Vec<StatModifier> {
+        self.0
+            .iter()
+            .filter_map(|effect| match &effect.kind {
+                EffectKind::Buf { stat, amount } => Some(StatModifier {
+                    stat: *stat,
+                    amount: amount.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Message, Debug, Clone)]
+pub struct EffectStarted(pub ActiveEffect);
+
+#[derive(Message, Debug, Clone)]
+pub struct EffectEnded(pub ActiveEffect);
+
+/// ticks the players effects, heals (or hurts) them, and keeps the buff & status layers of their
+/// stats up to date. in a fight they tick once a round, the same as the mobs, whatever the
+/// `TimeSource`.
+pub fn tick_effects(
+    mut clock: EffectClock,
+    mut player: Query<
+        (
+            &mut ActiveEffects,
+            &mut Health,
+            &EffectiveStats,
+            &mut StatModifiers,
+        ),
+        With<Player>,
+    >,
+    mut started_ev: MessageWriter<EffectStarted>,
+    mut ended_ev: MessageWriter<EffectEnded>,
+    ailments: AilmentData,
+) {
+    let elapsed = clock.elapsed();
+
+    let Ok((mut effects, mut health, stats, mut modifiers)) = player.single_mut() else {
+        return;
+    };
+
+    for effect in effects.0.iter_mut().filter(|effect| !effect.announced) {
+        effect.announced = true;
+        started_ev.write(EffectStarted(effect.clone()));
+    }
+
+    let tick = effects.tick(elapsed, stats.0.hp, |ailment| ailments.damage(ailment));
+
+    if tick.healed > 0.0 || tick.damage > 0.0 {
+        // ailments can wear you down, but they can't knock you out on their own.
+        let hp = health.0 + tick.healed - tick.damage;
+        health.0 = hp.min(stats.0.hp).max(health.0.min(1.0));
+    }
+
+    for effect in tick.ended {
+        ended_ev.write(EffectEnded(effect));
+    }
+
+    let buffs = effects.modifiers();
+
+    if modifiers.buffs != buffs {
+        modifiers.buffs = buffs;
+    }
+
+    let status = ailments.modifiers(&effects);
+
+    if modifiers.status != status {
+        modifiers.status = status;
+    }
+}
+
+/// how far along timed effects move each tick.
+#[derive(SystemParam)]
+pub struct EffectClock<'w, 's> {
+    time: Res<'w, Time>,
+    time_source: Res<'w, TimeSource>,
+    game_state: Res<'w, State<GameState>>,
+    turn_evs: MessageReader<'w, 's, TurnTaken>,
+}
+
+impl EffectClock<'_, '_> {
+    /// the seconds or turns since the last tick, depending on the `TimeSource`.
+    pub fn elapsed(&mut self) -> f32 {
+        let turns = self.turn_evs.read().count();
+
+        match *self.time_source {
+            _ if *self.game_state.get() == GameState::Battle => turns as f32,
+            TimeSource::RealTime => self.time.delta_secs(),
+            TimeSource::Turns => turns as f32,
+        }
+    }
+}
+
+/// lets the player know when an effect starts or wears off, ailments are called out louder.
+pub fn report_effects(
+    mut started_evs: MessageReader<EffectStarted>,
+    mut ended_evs: MessageReader<EffectEnded>,
+    mut notification_ev: MessageWriter<Notification>,
+) {
+    for EffectStarted(effect) in started_evs.read() {
+        info!("effect started: {effect}");
+
+        notification_ev.write(match effect.kind {
+            EffectKind::Ailment { ailment } => Notification::new(
+                NotificationLevel::Alert,
+                format!("You are afflicted with {ailment}."),
+            ),
+            _ => Notification::new(NotificationLevel::Normal, format!("{effect} took effect.")),
+        });
+    }
+
+    for EffectEnded(effect) in ended_evs.read() {
+        info!("effect ended: {effect}");
+        notification_ev.write(Notification::new(
+            NotificationLevel::Normal,
+            format!("{effect} wore off."),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ActiveEffect, ActiveEffects, EffectKind};
+    use crate::{
+        ailments::StatusAilment,
+        items::{Amount, Stat},
+    };
+
+    fn regen() -> ActiveEffect {
+        ActiveEffect::new(
+            "Regen Potion",
+            EffectKind::HealOverTime {
+                amount: Amount::Fixed(10),
+            },
+            5.0,
+        )
+    }
+
+    fn strength(duration: f32) -> ActiveEffect {
+        ActiveEffect::new(
+            "Strength Tonic",
+            EffectKind::Buf {
+                stat: Stat::Attack,
+                amount: Amount::Percent(0.1),
+            },
+            duration,
+        )
+    }
+
+    #[test]
+    fn heals_are_spread_over_the_duration() {
+        let mut effects = ActiveEffects::default();
+        effects.add(regen());
+
+        let mut healed = 0.0;
+
+        for _ in 0..4 {
+            let tick = effects.tick(1.0, 30.0, |_| None);
+            assert!(tick.ended.is_empty());
+            healed += tick.healed;
+        }
+
+        // a long last tick only heals what is left.
+        let tick = effects.tick(10.0, 30.0, |_| None);
+        healed += tick.healed;
+
+        assert!((healed - 10.0).abs() < 0.001, "healed {healed}");
+        assert_eq!(tick.ended.len(), 1);
+        assert!(effects.0.is_empty());
+    }
+
+    #[test]
+    fn heals_stack_and_buffs_refresh() {
+        let mut effects = ActiveEffects::default();
+        effects.add(regen());
+        effects.add(regen());
+        assert_eq!(effects.0.len(), 2);
+
+        effects.add(strength(3.0));
+        effects.tick(2.0, 30.0, |_| None);
+        effects.add(strength(3.0));
+
+        let buffs: Vec<_> = effects
+            .0
+            .iter()
+            .filter(|effect| matches!(effect.kind, EffectKind::Buf { .. }))
+            .collect();
+        assert_eq!(buffs.len(), 1);
+        assert_eq!(buffs[0].remaining, 3.0);
+        assert_eq!(effects.modifiers().len(), 1);
+    }
+
+    #[test]
+    fn ailments_hurt_refresh_and_cure() {
+        let poison = |source: &str| {
+            ActiveEffect::new(
+                source,
+                EffectKind::Ailment {
+                    ailment: StatusAilment::Poison,
+                },
+                5.0,
+            )
+        };
+        let damage_of = |ailment| match ailment {
+            StatusAilment::Poison => Some(Amount::Percent(0.1)),
+            _ => None,
+        };
+
+        let mut effects = ActiveEffects::default();
+        effects.add(poison("Goblin"));
+        effects.add(strength(5.0));
+        let tick = effects.tick(2.0, 30.0, damage_of);
+        assert!((tick.damage - 6.0).abs() < 0.001, "damage {}", tick.damage);
+
+        // the same ailment from somewhere else refreshes the running one.
+        effects.add(poison("Spider"));
+        assert_eq!(effects.ailments().count(), 1);
+        assert_eq!(effects.0[0].remaining, 5.0);
+
+        let cured = effects.cure(&[StatusAilment::Blind]);
+        assert!(cured.is_empty());
+        assert_eq!(effects.cure(&[]).len(), 1);
+        assert_eq!(effects.0.len(), 1, "curing leaves buffs alone");
+    }
+}
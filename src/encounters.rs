@@ -0,0 +1,147 @@
+use crate::{
+    TurnTaken,
+    battle::StartBattle,
+    mobs::{MobAsset, MobId, Mobs},
+    state::GameState,
+    zones::CurrentZone,
+};
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+/// the random number generator used to roll for encounters. seed it to get the same mobs every
+/// time.
+#[derive(Resource, Debug, Clone)]
+pub struct EncounterRng(pub StdRng);
+
+impl EncounterRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for EncounterRng {
+    fn default() -> Self {
+        Self(StdRng::from_os_rng())
+    }
+}
+
+/// decides if a mob shows up, and which one. `zone_rate` is the chance that anything shows up at
+/// all, then a mob is picked from `spawn_table`, weighted by each mobs spawn rate.
+pub fn roll_encounter<R: Rng>(
+    rng: &mut R,
+    zone_rate: f32,
+    spawn_table: &[(MobId, f32)],
+) -> Option<MobId> {
+    let total: f32 = spawn_table
+        .iter()
+        .map(|(_mob, weight)| weight.max(0.0))
+        .sum();
+
+    if zone_rate <= 0.0 || total <= 0.0 || rng.random::<f32>() >= zone_rate {
+        return None;
+    }
+
+    let mut pick = rng.random_range(0.0..total);
+
+    for (mob, weight) in spawn_table {
+        let weight = weight.max(0.0);
+
+        if pick < weight {
+            return Some(mob.clone());
+        }
+
+        pick -= weight;
+    }
+
+    // floating point rounding can leave a sliver at the end of the range.
+    spawn_table
+        .iter()
+        .rev()
+        .find(|(_mob, weight)| *weight > 0.0)
+        .map(|(mob, _weight)| mob.clone())
+}
+
+/// rolls for a random encounter every turn the player spends in a zone.
+pub fn roll_for_encounter(
+    mut turn_evs: MessageReader<TurnTaken>,
+    mut battle_ev: MessageWriter<StartBattle>,
+    mut rng: ResMut<EncounterRng>,
+    game_state: Res<State<GameState>>,
+    here: CurrentZone,
+    mob_assets: Res<Assets<MobAsset>>,
+    mobs: Res<Mobs>,
+) {
+    // a few commands in one frame still only count as one chance to get jumped.
+    if turn_evs.read().count() == 0
+        || !matches!(game_state.get(), GameState::Adventure | GameState::Dungeon)
+    {
+        return;
+    }
+
+    let Some(zone) = here.get() else {
+        return;
+    };
+
+    let spawn_table: Vec<(MobId, f32)> = zone
+        .mobs
+        .iter()
+        .filter_map(|mob_id| {
+            let mob = mob_assets.get(mobs.0.get(mob_id)?)?;
+            Some((mob_id.clone(), mob.spawn_rate))
+        })
+        .collect();
+
+    if let Some(mob) = roll_encounter(&mut rng.0, zone.mob_spawn_rate, &spawn_table) {
+        info!("the player ran into {mob} in {}", here.location.0);
+        battle_ev.write(StartBattle { mob });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EncounterRng, roll_encounter};
+
+    fn table() -> Vec<(String, f32)> {
+        vec![
+            ("mobs/goblin.mob.ron".into(), 0.025),
+            ("mobs/wolf.mob.ron".into(), 0.075),
+        ]
+    }
+
+    #[test]
+    fn seeded_rolls_are_repeatable() {
+        let mut a = EncounterRng::seeded(9000);
+        let mut b = EncounterRng::seeded(9000);
+
+        let rolls_a: Vec<_> = (0..100)
+            .map(|_| roll_encounter(&mut a.0, 0.5, &table()))
+            .collect();
+        let rolls_b: Vec<_> = (0..100)
+            .map(|_| roll_encounter(&mut b.0, 0.5, &table()))
+            .collect();
+
+        assert_eq!(rolls_a, rolls_b);
+        assert!(rolls_a.iter().any(|roll| roll.is_some()));
+        assert!(rolls_a.iter().any(|roll| roll.is_none()));
+    }
+
+    #[test]
+    fn spawn_rates_weight_the_pick() {
+        let mut rng = EncounterRng::seeded(42);
+        let goblins = (0..10_000)
+            .filter_map(|_| roll_encounter(&mut rng.0, 1.0, &table()))
+            .filter(|mob| mob == "mobs/goblin.mob.ron")
+            .count();
+
+        // goblins should show up about a quarter of the time.
+        assert!((2_000..3_000).contains(&goblins), "got {goblins} goblins");
+    }
+
+    #[test]
+    fn empty_zones_are_safe() {
+        let mut rng = EncounterRng::seeded(0);
+
+        assert_eq!(roll_encounter(&mut rng.0, 0.0, &table()), None);
+        assert_eq!(roll_encounter(&mut rng.0, 1.0, &[]), None);
+    }
+}
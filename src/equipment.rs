@@ -0,0 +1,358 @@
+use crate::{
+    items::{
+        EquipmentEffect, InventoryEntry, InventoryItems, ItemAsset, ItemType, Items, Stat,
+        WeaponHands, Wear, remove_from_stack, repair, wear,
+    },
+    player::{CombatStats, Player},
+    stats::{StatModifier, StatModifiers, apply_layer},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EquipSlot {
+    MainHand,
+    OffHand,
+    Armor,
+}
+
+impl EquipSlot {
+    pub const ALL: [EquipSlot; 3] = [Self::MainHand, Self::OffHand, Self::Armor];
+
+    /// the slots an item takes up when equipped. None if the item can't be equipped.
+    pub fn for_item(item: &ItemType) -> Option<&'static [EquipSlot]> {
+        match item {
+            ItemType::Weapon { hands, .. } => Some(match hands {
+                WeaponHands::MainHand => &[Self::MainHand],
+                WeaponHands::OffHand => &[Self::OffHand],
+                WeaponHands::TwoHands => &[Self::MainHand, Self::OffHand],
+            }),
+            ItemType::Armor { .. } => Some(&[Self::Armor]),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for EquipSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MainHand => write!(f, "Main Hand"),
+            Self::OffHand => write!(f, "Off Hand"),
+            Self::Armor => write!(f, "Armor"),
+        }
+    }
+}
+
+/// what the player is wielding & wearing. each slot points at an inventory entry. a two handed
+/// weapon sits in both hand slots at once.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Equipment {
+    pub main_hand: Option<Entity>,
+    pub off_hand: Option<Entity>,
+    pub armor: Option<Entity>,
+}
+
+impl Equipment {
+    pub fn get(&self, slot: EquipSlot) -> Option<Entity> {
+        match slot {
+            EquipSlot::MainHand => self.main_hand,
+            EquipSlot::OffHand => self.off_hand,
+            EquipSlot::Armor => self.armor,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: EquipSlot) -> &mut Option<Entity> {
+        match slot {
+            EquipSlot::MainHand => &mut self.main_hand,
+            EquipSlot::OffHand => &mut self.off_hand,
+            EquipSlot::Armor => &mut self.armor,
+        }
+    }
+
+    pub fn is_equipped(&self, item: Entity) -> bool {
+        EquipSlot::ALL
+            .into_iter()
+            .any(|slot| self.get(slot) == Some(item))
+    }
+
+    /// every equipped item, once each.
+    pub fn items(&self) -> Vec<Entity> {
+        let mut items: Vec<Entity> = Vec::new();
+
+        for item in EquipSlot::ALL.into_iter().filter_map(|slot| self.get(slot)) {
+            if !items.contains(&item) {
+                items.push(item);
+            }
+        }
+
+        items
+    }
+
+    /// puts an item in the given slots, returning the items that had to be taken off to make room.
+    /// taking off a two handed weapon frees up both hands.
+    pub fn equip(&mut self, item: Entity, slots: &[EquipSlot]) -> Vec<Entity> {
+        let mut removed = Vec::new();
+
+        for slot in slots {
+            if let Some(old) = self.get(*slot)
+                && old != item
+                && !removed.contains(&old)
+            {
+                self.unequip(old);
+                removed.push(old);
+            }
+        }
+
+        // an item moving from one set of slots to another (shouldn't happen, but still).
+        self.unequip(item);
+
+        for slot in slots {
+            *self.slot_mut(*slot) = Some(item);
+        }
+
+        removed
+    }
+
+    /// takes an item off, returns false if it wasn't equipped.
+    pub fn unequip(&mut self, item: Entity) -> bool {
+        let mut was_equipped = false;
+
+        for slot in EquipSlot::ALL {
+            let held = self.slot_mut(slot);
+
+            if *held == Some(item) {
+                *held = None;
+                was_equipped = true;
+            }
+        }
+
+        was_equipped
+    }
+}
+
+/// the slots of the equipment as indices into the saved inventory, so it can be rebuilt after a
+/// load.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedEquipment {
+    pub main_hand: Option<usize>,
+    pub off_hand: Option<usize>,
+    pub armor: Option<usize>,
+}
+
+impl SavedEquipment {
+    /// `inventory` is the order the inventory entries were saved in.
+    pub fn new(equipment: &Equipment, inventory: &[Entity]) -> Self {
+        let index = |slot| {
+            let item = equipment.get(slot)?;
+            inventory.iter().position(|entry| *entry == item)
+        };
+
+        Self {
+            main_hand: index(EquipSlot::MainHand),
+            off_hand: index(EquipSlot::OffHand),
+            armor: index(EquipSlot::Armor),
+        }
+    }
+
+    /// `inventory` is the entities the saved inventory entries were spawned as, in order.
+    pub fn restore(&self, inventory: &[Entity]) -> Equipment {
+        let entity = |index: Option<usize>| inventory.get(index?).copied();
+
+        Equipment {
+            main_hand: entity(self.main_hand),
+            off_hand: entity(self.off_hand),
+            armor: entity(self.armor),
+        }
+    }
+}
+
+/// the stat buffs an item gives while it's equipped.
+pub fn equipment_buffs(item: &ItemType) -> Vec<StatModifier> {
+    let effects = match item {
+        ItemType::Weapon { effects, .. } | ItemType::Armor { effects } => effects.as_slice(),
+        _ => &[],
+    };
+
+    effects
+        .iter()
+        .filter_map(|effect| match effect {
+            EquipmentEffect::BufUser { stat, amount, .. } => Some(StatModifier {
+                stat: *stat,
+                amount: amount.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// how the gear layer of the players stats would change going from wearing `now` to wearing
+/// `then`. only the stats that change are returned, as (stat, now, then).
+pub fn compare_equipment(
+    base: &CombatStats,
+    now: &[&ItemType],
+    then: &[&ItemType],
+) -> Vec<(Stat, f32, f32)> {
+    let stats_with = |worn: &[&ItemType]| {
+        let buffs: Vec<StatModifier> = worn.iter().flat_map(|item| equipment_buffs(item)).collect();
+        apply_layer(base, &buffs)
+    };
+    let (now, then) = (stats_with(now), stats_with(then));
+
+    Stat::ALL
+        .into_iter()
+        .filter(|stat| now.get(stat) != then.get(stat))
+        .map(|stat| (stat, now.get(&stat), then.get(&stat)))
+        .collect()
+}
+
+/// repairs the equipped items that wear down by `amount`, returning the names of what was repaired.
+pub fn repair_equipped(
+    cmds: &mut Commands,
+    equipment: &Equipment,
+    inventory: &InventoryItems,
+    amount: u32,
+) -> Vec<String> {
+    equipment
+        .items()
+        .into_iter()
+        .filter_map(|item| {
+            let (_, entry) = inventory.entries.get(item).ok()?;
+            let asset = inventory.items.get(&entry.asset_path)?;
+
+            if entry.durability_left(asset)? == asset.durability? {
+                return None;
+            }
+
+            cmds.entity(item).insert(InventoryEntry {
+                durability: repair(entry, asset, amount),
+                ..entry.clone()
+            });
+
+            Some(asset.name.clone())
+        })
+        .collect()
+}
+
+/// wears down the equipped weapons after a hit. returns the names of the weapons that broke, the
+/// last of a stack breaking takes it out of the players hands.
+pub fn wear_weapons(
+    cmds: &mut Commands,
+    equipment: &mut Equipment,
+    inventory: &InventoryItems,
+) -> Vec<String> {
+    let mut broke = Vec::new();
+
+    for item in equipment.items() {
+        let Ok((_, entry)) = inventory.entries.get(item) else {
+            continue;
+        };
+        let Some(asset) = inventory.items.get(&entry.asset_path) else {
+            continue;
+        };
+
+        if !matches!(asset.item_data, ItemType::Weapon { .. }) {
+            continue;
+        }
+
+        match wear(entry, asset) {
+            Wear::Unworn => {}
+            Wear::Worn(left) => {
+                cmds.entity(item).insert(InventoryEntry {
+                    durability: Some(left),
+                    ..entry.clone()
+                });
+            }
+            Wear::Broke => {
+                let fresh = InventoryEntry {
+                    durability: None,
+                    ..entry.clone()
+                };
+
+                if remove_from_stack(cmds, item, &fresh, 1) {
+                    equipment.unequip(item);
+                }
+
+                broke.push(asset.name.clone());
+            }
+        }
+    }
+
+    broke
+}
+
+/// turns the buffs of the equipped items in to the equipment layer of the players stat modifiers.
+pub fn update_equipment_modifiers(
+    mut player: Query<(&Equipment, &mut StatModifiers), With<Player>>,
+    inventory: Query<&InventoryEntry>,
+    item_assets: Res<Assets<ItemAsset>>,
+    items: Res<Items>,
+) {
+    let Ok((equipment, mut modifiers)) = player.single_mut() else {
+        return;
+    };
+
+    let buffs: Vec<StatModifier> = equipment
+        .items()
+        .into_iter()
+        .filter_map(|item| {
+            let entry = inventory.get(item).ok()?;
+            item_assets.get(items.0.get(&entry.asset_path)?)
+        })
+        .flat_map(|item| equipment_buffs(&item.item_data))
+        .collect();
+
+    if modifiers.equipment != buffs {
+        modifiers.equipment = buffs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EquipSlot, Equipment, compare_equipment};
+    use crate::{
+        items::{Amount, EquipmentEffect, ItemType, Stat, WeaponHands},
+        player::CombatStats,
+    };
+    use bevy::prelude::World;
+
+    #[test]
+    fn comparing_shows_changed_stats() {
+        let sword = |attack| ItemType::Weapon {
+            hands: WeaponHands::MainHand,
+            effects: vec![EquipmentEffect::BufUser {
+                stat: Stat::Attack,
+                amount: Amount::Fixed(attack),
+                duration: 0.0,
+            }],
+        };
+        let (old, new) = (sword(2), sword(5));
+        let base = CombatStats::default();
+
+        let diff = compare_equipment(&base, &[&old], &[&new]);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].0, Stat::Attack);
+        assert_eq!(diff[0].2 - diff[0].1, 3.0);
+        assert!(compare_equipment(&base, &[&old], &[&old]).is_empty());
+    }
+
+    #[test]
+    fn two_handed_weapons_take_both_hands() {
+        let mut world = World::new();
+        let [sword, shield, great_axe] = [(); 3].map(|_| world.spawn_empty().id());
+        let mut equipment = Equipment::default();
+
+        assert!(equipment.equip(sword, &[EquipSlot::MainHand]).is_empty());
+        assert!(equipment.equip(shield, &[EquipSlot::OffHand]).is_empty());
+
+        let removed = equipment.equip(great_axe, &[EquipSlot::MainHand, EquipSlot::OffHand]);
+        assert_eq!(removed, [sword, shield]);
+        assert_eq!(equipment.main_hand, Some(great_axe));
+        assert_eq!(equipment.off_hand, Some(great_axe));
+
+        // a shield pushes the axe out of both hands.
+        assert_eq!(equipment.equip(shield, &[EquipSlot::OffHand]), [great_axe]);
+        assert_eq!(equipment.main_hand, None);
+        assert_eq!(equipment.items(), [shield]);
+    }
+}
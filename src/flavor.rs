@@ -0,0 +1,145 @@
+use crate::{
+    GenerincFlavorText, HashMap,
+    zones::{FlavorTextId, FlavorTextType},
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlavorLine {
+    pub id: FlavorTextId,
+    /// what the line is about, ie. the command a help text explains.
+    #[serde(default)]
+    pub topic: Option<String>,
+    pub text: String,
+}
+
+/// a collection of flavor text of one type, ie. "assets/flavor/help.flavor.ron". more than one
+/// file can hold lines of the same type, ids only need to be unique within a type.
+#[derive(Debug, Clone, Serialize, Deserialize, Asset, TypePath)]
+pub struct FlavorAsset {
+    pub flavor_type: FlavorTextType,
+    pub lines: Vec<FlavorLine>,
+}
+
+#[derive(Debug, Clone, Default, Resource)]
+pub struct FlavorTexts(pub HashMap<String, Handle<FlavorAsset>>);
+
+/// the last line picked at random for each type of flavor text, so the same line isn't shown
+/// twice in a row.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct RecentFlavor(pub HashMap<FlavorTextType, FlavorTextId>);
+
+/// picks a random line, avoiding the `last` one picked unless it is the only one there is.
+pub fn pick_line<'a, R: Rng>(
+    lines: &[&'a FlavorLine],
+    last: Option<FlavorTextId>,
+    rng: &mut R,
+) -> Option<&'a FlavorLine> {
+    let fresh: Vec<&FlavorLine> = lines
+        .iter()
+        .copied()
+        .filter(|line| Some(line.id) != last)
+        .collect();
+
+    if fresh.is_empty() {
+        lines.choose(rng).copied()
+    } else {
+        fresh.choose(rng).copied()
+    }
+}
+
+/// looks up flavor text across every loaded flavor asset.
+#[derive(SystemParam)]
+pub struct FlavorLibrary<'w> {
+    texts: Res<'w, FlavorTexts>,
+    assets: Res<'w, Assets<FlavorAsset>>,
+    recent: ResMut<'w, RecentFlavor>,
+}
+
+impl FlavorLibrary<'_> {
+    /// every line of the given type.
+    pub fn lines(&self, flavor_type: FlavorTextType) -> impl Iterator<Item = &FlavorLine> {
+        self.texts
+            .0
+            .values()
+            .filter_map(|handle| self.assets.get(handle))
+            .filter(move |asset| asset.flavor_type == flavor_type)
+            .flat_map(|asset| asset.lines.iter())
+    }
+
+    /// the text of the first line about `topic`, if there is one.
+    pub fn topic(&self, flavor_type: FlavorTextType, topic: &str) -> Option<&str> {
+        self.lines(flavor_type)
+            .find(|line| line.topic.as_deref() == Some(topic))
+            .map(|line| line.text.as_str())
+    }
+
+    pub fn specific(&self, flavor_type: FlavorTextType, id: FlavorTextId) -> Option<&str> {
+        self.lines(flavor_type)
+            .find(|line| line.id == id)
+            .map(|line| line.text.as_str())
+    }
+
+    /// a random line of the given type, never the same one twice in a row.
+    pub fn random(&mut self, flavor_type: FlavorTextType) -> Option<String> {
+        let last = self.recent.0.get(&flavor_type).copied();
+        let lines: Vec<&FlavorLine> = self.lines(flavor_type).collect();
+        let line = pick_line(&lines, last, &mut rand::rng())?;
+        let (id, text) = (line.id, line.text.clone());
+        self.recent.0.insert(flavor_type, id);
+
+        Some(text)
+    }
+
+    /// turns flavor text from a command result into something to show the player. None if the
+    /// text it points at doesn't exist.
+    pub fn resolve(&mut self, flavor: &GenerincFlavorText) -> Option<String> {
+        match flavor {
+            GenerincFlavorText::Message(text) => Some(text.clone()),
+            GenerincFlavorText::Random(flavor_type) => self.random(*flavor_type),
+            GenerincFlavorText::Specific { flavor_type, id } => {
+                self.specific(*flavor_type, *id).map(String::from)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FlavorLine, pick_line};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn line(id: u64) -> FlavorLine {
+        FlavorLine {
+            id,
+            topic: None,
+            text: format!("line {id}"),
+        }
+    }
+
+    #[test]
+    fn random_lines_dont_repeat() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let lines = [line(0), line(1), line(2)];
+        let lines: Vec<&FlavorLine> = lines.iter().collect();
+
+        let mut last = None;
+
+        for _ in 0..50 {
+            let picked = pick_line(&lines, last, &mut rng).unwrap();
+            assert_ne!(Some(picked.id), last);
+            last = Some(picked.id);
+        }
+
+        let only = [line(4)];
+        let only: Vec<&FlavorLine> = only.iter().collect();
+        assert_eq!(
+            pick_line(&only, Some(4), &mut rng).map(|line| line.id),
+            Some(4),
+            "a lone line can repeat"
+        );
+        assert!(pick_line(&[], None, &mut rng).is_none());
+    }
+}
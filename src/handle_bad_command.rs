@@ -0,0 +1,64 @@
+use crate::{
+    CommandResultEvent,
+    commands::{
+        BadCommand,
+        commands::{BattleCmd, GameCmd, ShopCmd, SlashCmd},
+        suggest::suggest,
+    },
+    state::GameState,
+};
+use bevy::prelude::*;
+use clap::{CommandFactory, error::ErrorKind};
+
+/// tells the player their command didn't make sense, and what they might have meant instead.
+pub fn handle_bad_command(
+    mut bad_cmd_evs: MessageReader<BadCommand>,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+    game_state: Res<State<GameState>>,
+) {
+    for bad in bad_cmd_evs.read() {
+        let input = bad.input.trim();
+
+        if input.is_empty() {
+            continue;
+        }
+
+        debug!("bad command {input:?}: {}", bad.error);
+
+        let game_state = *game_state.get();
+        // battles & shops only have a handful of commands, none of which /help knows about.
+        let limited = matches!(game_state, GameState::Battle | GameState::Shopping);
+        // the same commands listener tried to parse the input as.
+        let root = if input.starts_with("/") {
+            SlashCmd::command()
+        } else if game_state == GameState::Battle {
+            BattleCmd::command()
+        } else if game_state == GameState::Shopping {
+            ShopCmd::command()
+        } else {
+            GameCmd::command()
+        };
+
+        let verb = input.split_whitespace().next().unwrap_or_default();
+        let incomplete = matches!(
+            bad.kind,
+            ErrorKind::MissingRequiredArgument
+                | ErrorKind::MissingSubcommand
+                | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        ) && root.find_subcommand(verb).is_some();
+
+        let mut message = match (incomplete, limited) {
+            (true, false) => {
+                format!("\"{verb}\" needs more than that. Try \"/help {verb}\".")
+            }
+            (true, true) => format!("\"{verb}\" needs more than that."),
+            (false, _) => format!("You don't know how to \"{input}\"."),
+        };
+
+        if let Some(suggestion) = suggest(&root, input) {
+            message.push_str(&format!(" Did you mean \"{suggestion}\"?"));
+        }
+
+        result_ev.write(CommandResultEvent::failure(message));
+    }
+}
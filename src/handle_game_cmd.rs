@@ -1,17 +1,43 @@
 use crate::{
-    /* CommandResultMessage, GenerincFlavorText, */ PlayerLook, PlayerMovement, PlayerTake,
-    commands::commands::GameCmd,
+    /* CommandResultMessage, GenerincFlavorText, */ CommandResultEvent, PlayerDrop,
+    PlayerEquip, PlayerExamine, PlayerInventory, PlayerLook, PlayerMovement, PlayerTake,
+    PlayerUnequip, PlayerUse, commands::commands::GameCmd, nouns::NounPhrase,
 };
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// where the commands that are done to an item get sent.
+#[derive(SystemParam)]
+pub struct ItemCmdWriters<'w> {
+    take_ev: MessageWriter<'w, PlayerTake>,
+    drop_ev: MessageWriter<'w, PlayerDrop>,
+    examine_ev: MessageWriter<'w, PlayerExamine>,
+    use_ev: MessageWriter<'w, PlayerUse>,
+    equip_ev: MessageWriter<'w, PlayerEquip>,
+    unequip_ev: MessageWriter<'w, PlayerUnequip>,
+}
 
 pub fn handle_game_cmd(
     mut commands: MessageReader<GameCmd>,
-    // mut cmd_res_ev: MessageWriter<CommandResultEvent>,
+    mut result_ev: MessageWriter<CommandResultEvent>,
     mut player_move_ev: MessageWriter<PlayerMovement>,
     mut player_look_ev: MessageWriter<PlayerLook>,
-    mut player_take_ev: MessageWriter<PlayerTake>,
+    mut player_inv_ev: MessageWriter<PlayerInventory>,
+    mut item_cmds: ItemCmdWriters,
 ) {
     for command in commands.read() {
+        // ie. "drop 3" or "take the", there's nothing to pick by.
+        if let Some((verb, phrase)) = named_thing(command)
+            && phrase.names_nothing()
+        {
+            result_ev.write(CommandResultEvent::failure(format!(
+                "{verb} {} what?",
+                phrase.to_string().trim()
+            )));
+            continue;
+        }
+
+        // the handlers send `TurnTaken` once they've actually changed something, so looking around
+        // or walking in to a wall is free.
         match command {
             GameCmd::Go { direction } => {
                 player_move_ev.write(PlayerMovement(direction.clone()));
@@ -19,20 +45,52 @@ pub fn handle_game_cmd(
             GameCmd::Look => {
                 player_look_ev.write_default();
             }
-            // GameCmd::Take { thing } => {
-            //     let thing = thing.join(" ");
-            //     info!("player is picking up {thing}");
-            //
-            //     cmd_res_ev.write(CommandResultEvent(Err(GenerincFlavorText::Message(
-            //             "you entered a valid command but picking-up/taking items isn't implemented yet.".into(),
-            //         )),
-            //     ));
-            // }
-            GameCmd::Take {} => {
-                player_take_ev.write_default();
-            } // GameCmd::Inventory {} => {
-              //     warn!("list all inventory items")
-              // }
+            GameCmd::Take { thing } => {
+                item_cmds
+                    .take_ev
+                    .write(PlayerTake(NounPhrase::parse(thing)));
+            }
+            GameCmd::Drop { thing } => {
+                item_cmds
+                    .drop_ev
+                    .write(PlayerDrop(NounPhrase::parse(thing)));
+            }
+            GameCmd::Inventory {} => {
+                player_inv_ev.write_default();
+            }
+            GameCmd::Examine { thing } => {
+                item_cmds
+                    .examine_ev
+                    .write(PlayerExamine(NounPhrase::parse(thing)));
+            }
+            GameCmd::Use { thing } => {
+                item_cmds.use_ev.write(PlayerUse(NounPhrase::parse(thing)));
+            }
+            GameCmd::Equip { thing } => {
+                item_cmds
+                    .equip_ev
+                    .write(PlayerEquip(NounPhrase::parse(thing)));
+            }
+            GameCmd::Unequip { thing } => {
+                item_cmds
+                    .unequip_ev
+                    .write(PlayerUnequip(NounPhrase::parse(thing)));
+            }
         }
     }
 }
+
+/// the verb & the thing it's done to, for commands that take one.
+fn named_thing(command: &GameCmd) -> Option<(&'static str, NounPhrase)> {
+    let (verb, thing) = match command {
+        GameCmd::Take { thing } => ("Take", thing),
+        GameCmd::Drop { thing } => ("Drop", thing),
+        GameCmd::Examine { thing } => ("Examine", thing),
+        GameCmd::Use { thing } => ("Use", thing),
+        GameCmd::Equip { thing } => ("Equip", thing),
+        GameCmd::Unequip { thing } => ("Unequip", thing),
+        GameCmd::Go { .. } | GameCmd::Look | GameCmd::Inventory {} => return None,
+    };
+
+    Some((verb, NounPhrase::parse(thing)))
+}
@@ -0,0 +1,103 @@
+use crate::{
+    PlayerDrop,
+    equipment::Equipment,
+    items::{InventoryItems, ItemId, count_each, one_per_item, remove_from_stack},
+    nouns::{CommandReply, Disambiguation, Picked, pick_item},
+    player::Player,
+    world::{GroundItem, WorldState},
+    zones::CurrentZone,
+};
+use bevy::prelude::*;
+
+/// moves items from the players inventory on to the ground of the zone they're in.
+pub fn handle_player_drop(
+    mut cmds: Commands,
+    mut player_drop_evs: MessageReader<PlayerDrop>,
+    mut reply: CommandReply,
+    inventory: InventoryItems,
+    mut player: Query<&mut Equipment, With<Player>>,
+    zone: CurrentZone,
+    mut world: ResMut<WorldState>,
+) {
+    let loc = &zone.location.0;
+
+    for PlayerDrop(thing) in player_drop_evs.read() {
+        let Some(zone_asset) = zone.get() else {
+            error!(
+                "the player is at a location that is unknown to the engine. something went VERY wrong."
+            );
+            error!("location: {loc}");
+            continue;
+        };
+
+        let mut equipment = player.single_mut().ok();
+        let is_equipped = |entity: Entity| {
+            equipment
+                .as_ref()
+                .is_some_and(|equipment| equipment.is_equipped(entity))
+        };
+
+        // one per item, so "drop 3 clubs" can take part of a stack.
+        let mut carried: Vec<((Entity, ItemId), String)> =
+            one_per_item(inventory.iter().map(|(entity, entry, asset)| {
+                (
+                    (entity, entry.asset_path.clone()),
+                    entry.count,
+                    asset.name.clone(),
+                )
+            }));
+        // spare items go before equipped ones, so "drop club" doesn't drop the one in your hand.
+        carried.sort_by_key(|((entity, _), _)| is_equipped(*entity));
+
+        if carried.is_empty() {
+            reply.failure("You aren't carrying anything.");
+            continue;
+        }
+
+        let dropped = match pick_item(thing, &carried, |(entity, _)| Some(*entity)) {
+            Picked::NoMatch => {
+                reply.failure(format!("You don't have a {}.", thing.name));
+                continue;
+            }
+            Picked::Ambiguous(options) => {
+                reply.ask(Disambiguation::new("drop", thing, options));
+                continue;
+            }
+            Picked::Things(dropped) => dropped,
+        };
+
+        let zone = world.zone_mut(loc, zone_asset);
+
+        for (_, item) in dropped.iter() {
+            info!("dropped: {item}");
+        }
+
+        for (entity, count) in count_each(dropped.iter().map(|(entity, _)| *entity)) {
+            let Ok((_, entry)) = inventory.entries.get(entity) else {
+                continue;
+            };
+            let gone = remove_from_stack(&mut cmds, entity, entry, count);
+
+            // the wear is on the top of the stack, so it only leaves with the last of the stack.
+            zone.items.push(GroundItem {
+                item: entry.asset_path.clone(),
+                durability: if gone { entry.durability } else { None },
+            });
+            zone.items
+                .extend((1..count).map(|_| GroundItem::new(entry.asset_path.clone())));
+
+            // the last of an equipped stack takes the equipment with it.
+            if gone && let Some(equipment) = equipment.as_mut() {
+                equipment.unequip(entity);
+            }
+        }
+
+        let dropped: Vec<ItemId> = dropped.into_iter().map(|(_, item)| item).collect();
+
+        reply.success(format!(
+            "You drop: {}.",
+            inventory.items.describe(&dropped).unwrap_or_default()
+        ));
+        reply.took_turn();
+    }
+}
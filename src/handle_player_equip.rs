@@ -0,0 +1,120 @@
+use crate::{
+    PlayerEquip, PlayerUnequip,
+    equipment::{EquipSlot, Equipment},
+    items::{InventoryItems, ItemAsset},
+    nouns::{CommandReply, Disambiguation, Picked, pick_item},
+    player::Player,
+};
+use bevy::prelude::*;
+
+/// wields a weapon or puts on armor from the inventory, putting away whatever was in the way.
+pub fn handle_player_equip(
+    mut player_equip_evs: MessageReader<PlayerEquip>,
+    mut reply: CommandReply,
+    mut player: Query<&mut Equipment, With<Player>>,
+    inventory: InventoryItems,
+) {
+    for PlayerEquip(thing) in player_equip_evs.read() {
+        let Ok(mut equipment) = player.single_mut() else {
+            error!("there is no player to equip items");
+            continue;
+        };
+
+        let mut carried: Vec<((Entity, &ItemAsset), String)> = inventory
+            .iter()
+            .map(|(entity, _, asset)| ((entity, asset), asset.name.clone()))
+            .collect();
+        // spare items first, so "equip club" picks one that isn't already in your hand.
+        carried.sort_by_key(|((entity, _), _)| equipment.is_equipped(*entity));
+
+        let (entity, asset) = match pick_item(thing, &carried, |(entity, _)| Some(*entity)) {
+            Picked::Things(picked) if !picked.is_empty() => picked[0],
+            Picked::Ambiguous(options) => {
+                reply.ask(Disambiguation::new("equip", thing, options));
+                continue;
+            }
+            _ => {
+                reply.failure(format!("You don't have a {}.", thing.name));
+                continue;
+            }
+        };
+
+        if equipment.is_equipped(entity) {
+            reply.failure(format!("You already have the {} equipped.", asset.name));
+            continue;
+        }
+
+        let Some(slots) = EquipSlot::for_item(&asset.item_data) else {
+            reply.failure(format!("You can't equip the {}.", asset.name));
+            continue;
+        };
+
+        let removed: Vec<&str> = equipment
+            .equip(entity, slots)
+            .into_iter()
+            .filter_map(|old| {
+                carried
+                    .iter()
+                    .find(|((other, _), _)| *other == old)
+                    .map(|(_, name)| name.as_str())
+            })
+            .collect();
+
+        let mut message = format!("You equip the {}.", asset.name);
+
+        if !removed.is_empty() {
+            message = format!("You put away the {}. {message}", removed.join(" and the "));
+        }
+
+        info!("equipped: {}", asset.name);
+        reply.success(message);
+        reply.took_turn();
+    }
+}
+
+/// puts away equipped weapons & armor.
+pub fn handle_player_unequip(
+    mut player_unequip_evs: MessageReader<PlayerUnequip>,
+    mut reply: CommandReply,
+    mut player: Query<&mut Equipment, With<Player>>,
+    inventory: InventoryItems,
+) {
+    for PlayerUnequip(thing) in player_unequip_evs.read() {
+        let Ok(mut equipment) = player.single_mut() else {
+            error!("there is no player to unequip items");
+            continue;
+        };
+
+        let equipped: Vec<(Entity, String)> = equipment
+            .items()
+            .into_iter()
+            .filter_map(|entity| {
+                let (_, entry) = inventory.entries.get(entity).ok()?;
+                let asset = inventory.items.get(&entry.asset_path)?;
+                Some((entity, asset.name.clone()))
+            })
+            .collect();
+
+        let unequipped = match pick_item(thing, &equipped, |entity| Some(*entity)) {
+            Picked::Things(picked) if !picked.is_empty() => picked,
+            Picked::Ambiguous(options) => {
+                reply.ask(Disambiguation::new("unequip", thing, options));
+                continue;
+            }
+            _ => {
+                reply.failure(format!("You don't have a {} equipped.", thing.name));
+                continue;
+            }
+        };
+
+        let names: Vec<&str> = equipped
+            .iter()
+            .filter(|(entity, _)| unequipped.contains(entity) && equipment.unequip(*entity))
+            .map(|(_, name)| name.as_str())
+            .collect();
+
+        info!("unequipped: {names:?}");
+        reply.success(format!("You put away the {}.", names.join(" and the ")));
+        reply.took_turn();
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{
+    PlayerExamine,
+    items::{InventoryItems, ItemAsset},
+    nouns::{CommandReply, Disambiguation, Picked, pick_item},
+    world::WorldState,
+    zones::CurrentZone,
+};
+use bevy::prelude::*;
+
+/// shows the description of an item the player is carrying, or one lying on the ground.
+pub fn handle_player_examine(
+    mut player_examine_evs: MessageReader<PlayerExamine>,
+    mut reply: CommandReply,
+    inventory: InventoryItems,
+    zone: CurrentZone,
+    world: Res<WorldState>,
+) {
+    let loc = &zone.location.0;
+
+    for PlayerExamine(thing) in player_examine_evs.read() {
+        let on_ground = zone
+            .get()
+            .map(|zone_asset| world.items_in(loc, zone_asset))
+            .unwrap_or_default();
+
+        // the inventory is checked first, so "examine club" means the one in your bag.
+        let in_reach: Vec<((Option<Entity>, &ItemAsset), String)> = inventory
+            .entries
+            .iter()
+            .map(|(entity, entry)| (Some(entity), &entry.asset_path))
+            .chain(on_ground.iter().map(|on_ground| (None, &on_ground.item)))
+            .filter_map(|(entity, item_id)| {
+                let asset = inventory.items.get(item_id)?;
+                Some(((entity, asset), asset.name.clone()))
+            })
+            .collect();
+
+        let asset = match pick_item(thing, &in_reach, |(entity, _)| *entity) {
+            Picked::Things(assets) if !assets.is_empty() => assets[0].1,
+            Picked::Ambiguous(options) => {
+                reply.ask(Disambiguation::new("examine", thing, options));
+                continue;
+            }
+            _ => {
+                reply.failure(format!("You don't see a {} anywhere.", thing.name));
+                continue;
+            }
+        };
+
+        let mut text = format!("{}: {}", asset.name, asset.description);
+
+        if let Some(examine) = &asset.examine {
+            text = format!("{text}\n\n{examine}");
+        }
+
+        reply.success(text);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    PlayerInventory,
+    equipment::Equipment,
+    items::{InventoryEntry, ItemAsset, Items},
+    player::Player,
+    ui::update::UpdateMainSectionText,
+};
+use bevy::prelude::*;
+
+/// lists what the player is carrying in the main text panel.
+pub fn handle_player_inventory(
+    mut player_inv_evs: MessageReader<PlayerInventory>,
+    mut update_event: MessageWriter<UpdateMainSectionText>,
+    inventory: Query<(Entity, &InventoryEntry)>,
+    player: Query<&Equipment, With<Player>>,
+    item_assets: Res<Assets<ItemAsset>>,
+    items: Res<Items>,
+) {
+    for _ev in player_inv_evs.read() {
+        let equipment = player.single().ok();
+        // (name, equipped, count)
+        let mut carried: Vec<(String, bool, usize)> = Vec::new();
+
+        for (entity, entry) in inventory.iter() {
+            let equipped = equipment.is_some_and(|equipment| equipment.is_equipped(entity));
+            let Some(asset) = items
+                .0
+                .get(&entry.asset_path)
+                .and_then(|item| item_assets.get(item))
+            else {
+                warn!("unknown item {:?} in the inventory", entry.asset_path);
+                continue;
+            };
+
+            match carried
+                .iter_mut()
+                .find(|(name, is_equipped, _)| *name == asset.name && *is_equipped == equipped)
+            {
+                Some((_, _, count)) => *count += entry.count,
+                None => carried.push((asset.name.clone(), equipped, entry.count)),
+            }
+        }
+
+        if carried.is_empty() {
+            update_event.write(UpdateMainSectionText(
+                "You aren't carrying anything.".into(),
+            ));
+            continue;
+        }
+
+        carried.sort();
+        let lines: Vec<String> = carried
+            .into_iter()
+            .map(|(name, equipped, count)| {
+                let count = if count > 1 {
+                    format!(" (x{count})")
+                } else {
+                    String::new()
+                };
+                let equipped = if equipped { " [equipped]" } else { "" };
+
+                format!("  {name}{count}{equipped}")
+            })
+            .collect();
+
+        update_event.write(UpdateMainSectionText(format!(
+            "You are carrying:\n\n{}",
+            lines.join("\n")
+        )));
+    }
+}
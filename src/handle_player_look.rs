@@ -1,36 +1,68 @@
 use crate::{
-    PlayerLook,
-    ui::update::UpdateLookSectionText,
-    zones::{Location, ZoneAsset, Zones},
+    CommandResultEvent, PlayerLook,
+    ailments::{AilmentData, obscure},
+    effects::ActiveEffects,
+    items::ItemData,
+    player::Player,
+    world::WorldState,
+    zones::CurrentZone,
 };
 use bevy::prelude::*;
 
 /// displays more information to the player.
 pub fn handle_player_look(
     mut player_move_events: MessageReader<PlayerLook>,
-    mut look_event: MessageWriter<UpdateLookSectionText>,
-    zone_assets: Res<Assets<ZoneAsset>>,
-    zones: Res<Zones>,
-    location: Res<Location>,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+    zone: CurrentZone,
+    world: Res<WorldState>,
+    items: ItemData,
+    player: Query<&ActiveEffects, With<Player>>,
+    ailments: AilmentData,
 ) {
-    let loc = location.0.clone();
+    let loc = &zone.location.0;
 
     for _ev in player_move_events.read() {
-        if let Some(at) = zones.0.get(&loc) {
+        if zone.zones.contains(loc) {
             // get where they are and the "examine" text in one step.
-            let Some(look_text) = zone_assets.get(at).map(|asset| asset.examine.clone()) else {
+            let Some(zone_asset) = zone.get() else {
                 continue;
             };
 
-            let look_text =
-                look_text.unwrap_or("You looked around and saw nothing else of interest...".into());
-            look_event.write(UpdateLookSectionText(look_text));
+            let mut look_text = zone_asset
+                .examine
+                .clone()
+                .unwrap_or("You looked around and saw nothing else of interest...".into());
+
+            if let Some(legible) = player
+                .single()
+                .ok()
+                .and_then(|effects| ailments.legibility(effects))
+            {
+                look_text = obscure(&look_text, legible);
+            }
+
+            if let Some(on_ground) = items.describe(
+                world
+                    .items_in(loc, zone_asset)
+                    .iter()
+                    .map(|on_ground| &on_ground.item),
+            ) {
+                look_text = format!("{look_text}\n\nOn the ground you see: {on_ground}.");
+            }
+
+            if !zone_asset.places.is_empty() {
+                let mut places: Vec<&str> = zone_asset.places.keys().map(String::as_str).collect();
+                places.sort();
+                look_text = format!("{look_text}\nYou could go in: {}.", places.join(", "));
+            }
+
+            result_ev.write(CommandResultEvent::success(look_text));
             info!("player took a closer look at zone {loc:?}");
         } else {
             error!(
                 "the player is at a location that is unknown to the engine. something went VERY wrong."
             );
-            error!("{zones:?}.get({loc})")
+            error!("location: {loc}")
         }
     }
 }
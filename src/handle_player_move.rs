@@ -1,61 +1,126 @@
 use crate::{
-    NewZone, PlayerMovement,
+    Autosave, CommandResultEvent, NewZone, PlayerMovement, TurnTaken,
+    ailments::{AilmentData, obscure},
     commands::commands::Direction,
+    effects::ActiveEffects,
+    flavor::FlavorLibrary,
+    player::Player,
+    save::AutosaveConfig,
+    shops::EnterShop,
     state::GameState,
     ui::{
-        CompassDownText, CompassEastText, CompassNorthEastText, CompassNorthText,
-        CompassNorthWestText, CompassSouthEastText, CompassSouthText, CompassSouthWestText,
-        CompassUpText, CompassWestText, LookTextBody, update::UpdateMainSectionText,
+        CompassText,
+        update::{UpdateLookSectionText, UpdateMainSectionText},
     },
-    zones::{Location, ZoneAsset, Zones},
+    zones::{CurrentZone, FlavorTextType, Location, ZoneAsset, ZoneData, ZoneId, Zones},
 };
 use bevy::{
     color::palettes::tailwind::{AMBER_500, GRAY_500},
+    ecs::system::SystemParam,
     prelude::*,
 };
 
+/// moves the player to another zone, & everything that comes with arriving somewhere new.
+#[derive(SystemParam)]
+pub struct Travel<'w> {
+    location: ResMut<'w, Location>,
+    new_zone_ev: MessageWriter<'w, NewZone>,
+    look_event: MessageWriter<'w, UpdateLookSectionText>,
+    autosave_ev: MessageWriter<'w, Autosave>,
+    autosave_config: Res<'w, AutosaveConfig>,
+}
+
+impl Travel<'_> {
+    /// where the player is.
+    pub fn location(&self) -> &ZoneId {
+        &self.location.0
+    }
+
+    pub fn go_to(&mut self, zone_id: &ZoneId) {
+        self.location.0 = zone_id.clone();
+        self.look_event.write(UpdateLookSectionText(String::new()));
+        self.new_zone_ev.write_default();
+
+        if self.autosave_config.on_zone_change {
+            self.autosave_ev.write_default();
+        }
+    }
+}
+
 /// moves players from zone to zone
 pub fn handle_player_movement(
     mut player_move_events: MessageReader<PlayerMovement>,
-    mut new_zone_ev: MessageWriter<NewZone>,
-    zone_assets: Res<Assets<ZoneAsset>>,
-    zones: Res<Zones>,
-    mut location: ResMut<Location>,
-    mut look_text: Query<&mut Text, With<LookTextBody>>,
+    mut enter_shop_ev: MessageWriter<EnterShop>,
+    zones: ZoneData,
+    mut travel: Travel,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+    mut turn_ev: MessageWriter<TurnTaken>,
+    mut flavor: FlavorLibrary,
 ) {
-    let loc = location.0.clone();
+    let loc = travel.location().clone();
 
     for ev in player_move_events.read() {
-        if let Some(from) = zones.0.get(&loc) {
+        if zones.contains(&loc) {
             // get where they're going based on direction
             // set players location to the new one
-            let Some(Some(new_zone_asset_path)) = zone_assets
-                .get(from)
-                .map(|asset| asset.connections.get(&ev.0))
-            else {
-                // TODO: tell the player they cant go there
+            let Some(from) = zones.get(&loc) else {
                 continue;
             };
-            if zones.0.get(new_zone_asset_path).is_some() {
-                location.0 = new_zone_asset_path.to_owned();
-                _ = look_text
-                    .single_mut()
-                    .map(|mut text| text.0 = String::new());
+
+            // places aren't zones, going in one opens it up instead.
+            if let Direction::In { place } = &ev.0
+                && let Some(shop) = from.place(place)
+            {
+                debug!("player went in {place}");
+                enter_shop_ev.write(EnterShop { shop: shop.clone() });
+                continue;
+            }
+
+            let Some(new_zone_asset_path) = from.connections.get(&ev.0) else {
+                let flavor = flavor
+                    .random(FlavorTextType::CantGo)
+                    .unwrap_or("You can't go {direction} from here.".into());
+                result_ev.write(CommandResultEvent::failure(cant_go(&flavor, &ev.0, from)));
+                continue;
+            };
+            if zones.contains(new_zone_asset_path) {
+                travel.go_to(new_zone_asset_path);
+                turn_ev.write_default();
                 debug!("player moved {:?}", ev.0);
-                new_zone_ev.write_default();
             } else {
                 error!("player tried to move {:?}, but failed.", ev.0);
-                debug!("{zones:?}.get({new_zone_asset_path})")
+                debug!("no zone at {new_zone_asset_path}")
             }
         } else {
             error!(
                 "the player is at a location that is unknown to the engine. something went VERY wrong."
             );
-            error!("{zones:?}.get({loc})")
+            error!("location: {loc}")
         }
     }
 }
 
+/// fills the direction into a `CantGo` flavor line, ie. "You can't go west from here.", then adds
+/// the ways the player can go, including any places they can go in.
+fn cant_go(flavor: &str, direction: &Direction, zone: &ZoneAsset) -> String {
+    let line = flavor.replace("{direction}", &direction.to_string());
+    let mut exits: Vec<&Direction> = zone.connections.keys().collect();
+    exits.sort();
+    let mut places: Vec<&String> = zone.places.keys().collect();
+    places.sort();
+    let exits: Vec<String> = exits
+        .into_iter()
+        .map(|exit| exit.to_string())
+        .chain(places.into_iter().map(|place| format!("in {place}")))
+        .collect();
+
+    match exits.as_slice() {
+        [] => format!("{line} There is no way out of here at all."),
+        [exit] => format!("{line} You can go {exit}."),
+        [first @ .., last] => format!("{line} You can go {} or {last}.", first.join(", ")),
+    }
+}
+
 pub fn send_new_zone(
     mut new_zone_ev: MessageWriter<NewZone>,
     zone_assets: Res<Assets<ZoneAsset>>,
@@ -69,228 +134,54 @@ pub fn send_new_zone(
     }
 }
 
-/// updated the main text display
+/// updated the main text display. ailments like blindness garble the description, so it is also
+/// redrawn when they come & go.
 pub fn set_main_body(
     mut new_zone_evs: MessageReader<NewZone>,
-    zone_assets: Res<Assets<ZoneAsset>>,
-    zones: Res<Zones>,
-    location: Res<Location>,
+    zone: CurrentZone,
     mut update_event: MessageWriter<UpdateMainSectionText>,
+    player: Query<&ActiveEffects, With<Player>>,
+    ailments: AilmentData,
+    mut shown_legibility: Local<Option<f32>>,
 ) {
-    for _ev in new_zone_evs.read() {
-        zone_assets
-            .get(zones.0.get(&location.0).unwrap())
-            .map(|zone_asset| {
-                update_event.write(UpdateMainSectionText(zone_asset.description.clone()));
-            });
+    let legibility = player
+        .single()
+        .ok()
+        .and_then(|effects| ailments.legibility(effects));
+    let redraw = new_zone_evs.read().count() > 0 || *shown_legibility != legibility;
+    *shown_legibility = legibility;
+
+    if redraw && let Some(zone_asset) = zone.get() {
+        let description = match legibility {
+            Some(legible) => obscure(&zone_asset.description, legible),
+            None => zone_asset.description.clone(),
+        };
+
+        update_event.write(UpdateMainSectionText(description));
     }
 }
 
-/// moves players from zone to zone
+/// lights up the ways the player can go.
 pub fn compass_update(
     mut new_zone_evs: MessageReader<NewZone>,
-    zone_assets: Res<Assets<ZoneAsset>>,
-    zones: Res<Zones>,
-    location: Res<Location>,
-    // mut text_body: Query<&mut Text, With<LookTextBody>>,
-    mut c_u: Query<
-        &mut TextColor,
-        (
-            With<CompassUpText>,
-            // Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_d: Query<
-        &mut TextColor,
-        (
-            With<CompassDownText>,
-            Without<CompassUpText>,
-            // Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_n: Query<
-        &mut TextColor,
-        (
-            With<CompassNorthText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            // Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_s: Query<
-        &mut TextColor,
-        (
-            With<CompassSouthText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            // Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_e: Query<
-        &mut TextColor,
-        (
-            With<CompassEastText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            // Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_w: Query<
-        &mut TextColor,
-        (
-            With<CompassWestText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            // Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_ne: Query<
-        &mut TextColor,
-        (
-            With<CompassNorthEastText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            // Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_nw: Query<
-        &mut TextColor,
-        (
-            With<CompassNorthWestText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            // Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_se: Query<
-        &mut TextColor,
-        (
-            With<CompassSouthEastText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            // Without<CompassSouthEastText>,
-            Without<CompassSouthWestText>,
-        ),
-    >,
-    mut c_sw: Query<
-        &mut TextColor,
-        (
-            With<CompassSouthWestText>,
-            Without<CompassUpText>,
-            Without<CompassDownText>,
-            Without<CompassNorthText>,
-            Without<CompassSouthText>,
-            Without<CompassEastText>,
-            Without<CompassWestText>,
-            Without<CompassNorthEastText>,
-            Without<CompassNorthWestText>,
-            Without<CompassSouthEastText>,
-            // Without<CompassSouthWestText>,
-        ),
-    >,
+    zone: CurrentZone,
+    mut compass: Query<(&mut TextColor, &CompassText)>,
 ) {
-    let loc = location.0.clone();
-
     for _ev in new_zone_evs.read() {
-        let colors = [
-            (Direction::Up, c_u.single_mut()),
-            (Direction::Down, c_d.single_mut()),
-            (Direction::North, c_n.single_mut()),
-            (Direction::South, c_s.single_mut()),
-            (Direction::East, c_e.single_mut()),
-            (Direction::West, c_w.single_mut()),
-            (Direction::NorthEast, c_ne.single_mut()),
-            (Direction::NorthWest, c_nw.single_mut()),
-            (Direction::SouthEast, c_se.single_mut()),
-            (Direction::SouthWest, c_sw.single_mut()),
-        ];
-
-        if let Some(from) = zones.0.get(&loc) {
-            // get where they're going based on direction
-            // set players location to the new one
-            let Some(connections) = zone_assets.get(from).map(|asset| asset.connections.clone())
-            else {
-                // TODO: tell the player they cant go there
-                continue;
-            };
-
-            for (dir, color) in colors {
-                if connections.contains_key(&dir) {
-                    _ = color.map(|mut color| color.0 = AMBER_500.into());
-                } else {
-                    _ = color.map(|mut color| color.0 = GRAY_500.into());
-                }
-            }
-        } else {
+        let Some(zone_asset) = zone.get() else {
             error!(
                 "the player is at a location that is unknown to the engine. something went VERY wrong."
             );
-            error!("{zones:?}.get({loc})")
+            error!("location: {}", zone.location.0);
+            continue;
+        };
+
+        for (mut color, CompassText(dir)) in compass.iter_mut() {
+            color.0 = if zone_asset.connections.contains_key(dir) {
+                AMBER_500.into()
+            } else {
+                GRAY_500.into()
+            };
         }
     }
 }
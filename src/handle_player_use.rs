@@ -0,0 +1,128 @@
+use crate::{
+    PlayerUse,
+    ailments::{AilmentData, Hindrance, ProcRng},
+    effects::ActiveEffects,
+    equipment::{Equipment, repair_equipped},
+    items::{
+        InventoryItems, ItemAsset, ItemType, count_each, one_per_item, remove_from_stack,
+        repair_amount,
+    },
+    nouns::{CommandReply, Disambiguation, Picked, pick_item},
+    player::{EffectiveStats, Health, Player, apply_consumable},
+};
+use bevy::prelude::*;
+
+/// uses consumables and key items from the inventory. consumables are used up, key items are not.
+pub fn handle_player_use(
+    mut cmds: Commands,
+    mut player_use_evs: MessageReader<PlayerUse>,
+    mut reply: CommandReply,
+    inventory: InventoryItems,
+    mut player: Query<(&EffectiveStats, &mut Health, &mut ActiveEffects, &Equipment), With<Player>>,
+    mut rng: ResMut<ProcRng>,
+    ailments: AilmentData,
+) {
+    for PlayerUse(thing) in player_use_evs.read() {
+        let Ok((stats, mut health, mut active, equipment)) = player.single_mut() else {
+            error!("there is no player to use items");
+            continue;
+        };
+
+        if ailments.hinders(&active, Hindrance::UsingItems) {
+            reply.failure("You can't use items right now.");
+            continue;
+        }
+
+        // one per item, so "use 2 potions" works on a stack.
+        let carried: Vec<((Entity, &ItemAsset), String)> = one_per_item(
+            inventory
+                .iter()
+                .map(|(entity, entry, asset)| ((entity, asset), entry.count, asset.name.clone())),
+        );
+
+        let used = match pick_item(thing, &carried, |(entity, _)| Some(*entity)) {
+            Picked::Things(used) if !used.is_empty() => used,
+            Picked::Ambiguous(options) => {
+                reply.ask(Disambiguation::new("use", thing, options));
+                continue;
+            }
+            _ => {
+                reply.failure(format!("You don't have a {}.", thing.name));
+                continue;
+            }
+        };
+
+        let mut lines = Vec::new();
+        // the stack of every consumable used.
+        let mut used_up: Vec<Entity> = Vec::new();
+        // added up & done once, the inventory query doesn't see repairs made in this system.
+        let mut repairs = 0;
+        // fiddling with something that does nothing isn't a turn.
+        let mut used_any = false;
+
+        for (entity, asset) in used {
+            repairs += repair_amount(&asset.item_data);
+
+            match &asset.item_data {
+                ItemType::Consumable { .. } => {
+                    lines.extend(apply_consumable(
+                        asset,
+                        &mut health,
+                        &stats.0,
+                        &mut active,
+                        &mut rng.0,
+                    ));
+
+                    used_up.push(entity);
+                    used_any = true;
+                }
+                ItemType::KeyItem { effects } if !effects.is_empty() => {
+                    lines.extend(apply_consumable(
+                        asset,
+                        &mut health,
+                        &stats.0,
+                        &mut active,
+                        &mut rng.0,
+                    ));
+                    used_any = true;
+                }
+                ItemType::KeyItem { .. } => {
+                    lines.push(format!(
+                        "You fiddle with the {}. Nothing happens.",
+                        asset.name
+                    ));
+                }
+                ItemType::Weapon { .. } | ItemType::Armor { .. } => {
+                    lines.push(format!(
+                        "You can't use the {}, try equipping it instead.",
+                        asset.name
+                    ));
+                    // the rest would say the same thing.
+                    break;
+                }
+            }
+        }
+
+        if repairs > 0 {
+            let repaired = repair_equipped(&mut cmds, equipment, &inventory, repairs);
+
+            lines.push(if repaired.is_empty() {
+                "Your gear didn't need fixing.".into()
+            } else {
+                format!("Repaired: {}.", repaired.join(", "))
+            });
+        }
+
+        for (entity, count) in count_each(used_up) {
+            if let Ok((_, entry)) = inventory.entries.get(entity) {
+                remove_from_stack(&mut cmds, entity, entry, count);
+            }
+        }
+
+        reply.success(lines.join("\n"));
+
+        if used_any {
+            reply.took_turn();
+        }
+    }
+}
@@ -1,10 +1,46 @@
-use crate::{ChangeScreen, ExitGame, commands::commands::SlashCmd};
-use bevy::prelude::*;
+use crate::{
+    ChangeScreen, CommandResultEvent, ExitGame, LoadGame, SaveGame,
+    commands::commands::{SlashCmd, Toggle},
+    effects::TimeSource,
+    flavor::FlavorLibrary,
+    help::{help_for, help_index},
+    save::{AutosaveConfig, SaveSlot, newest_autosave},
+    zones::FlavorTextType,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// the options the player can change with slash commands.
+#[derive(SystemParam)]
+pub struct Settings<'w> {
+    autosave_config: ResMut<'w, AutosaveConfig>,
+    time_source: ResMut<'w, TimeSource>,
+}
+
+/// asks for the game to be saved to, or loaded from, a slot.
+#[derive(SystemParam)]
+pub struct SaveRequests<'w> {
+    save_ev: MessageWriter<'w, SaveGame>,
+    load_ev: MessageWriter<'w, LoadGame>,
+}
+
+impl SaveRequests<'_> {
+    pub fn save(&mut self, slot: SaveSlot) {
+        self.save_ev.write(SaveGame(slot));
+    }
+
+    pub fn load(&mut self, slot: SaveSlot) {
+        self.load_ev.write(LoadGame(slot));
+    }
+}
 
 pub fn slash_cmd(
     mut commands: MessageReader<SlashCmd>,
     mut exit_ev: MessageWriter<ExitGame>,
     mut view_ev: MessageWriter<ChangeScreen>,
+    mut saves: SaveRequests,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+    mut settings: Settings,
+    flavor: FlavorLibrary,
     // mut player_look_ev: MessageWriter<PlayerLook>,
 ) {
     for command in commands.read() {
@@ -12,11 +48,47 @@ pub fn slash_cmd(
             SlashCmd::Exit {} => {
                 exit_ev.write_default();
             }
-            SlashCmd::Help { with: _ } => {
-                // TODO: get help
+            SlashCmd::Help { with: None } => {
+                result_ev.write(CommandResultEvent::success(help_index()));
+            }
+            SlashCmd::Help { with: Some(topic) } => {
+                let long_help = flavor.topic(FlavorTextType::Help, &topic.name());
+                result_ev.write(CommandResultEvent::success(help_for(*topic, long_help)));
+            }
+            SlashCmd::Save { save_slot } => {
+                saves.save(SaveSlot::Manual(*save_slot));
+            }
+            SlashCmd::Load { save_slot } => {
+                saves.load(SaveSlot::Manual(*save_slot));
+            }
+            SlashCmd::Resume {} => {
+                if let Some((slot, _modified)) =
+                    newest_autosave(settings.autosave_config.generations)
+                {
+                    saves.load(slot);
+                } else {
+                    result_ev.write(CommandResultEvent::failure(
+                        "There is no autosave to resume.",
+                    ));
+                }
+            }
+            SlashCmd::Autosave { on_zone_change } => {
+                settings.autosave_config.on_zone_change = *on_zone_change == Toggle::On;
+                result_ev.write(CommandResultEvent::success(format!(
+                    "Autosaving on zone change is now {}.",
+                    if settings.autosave_config.on_zone_change {
+                        "on"
+                    } else {
+                        "off"
+                    }
+                )));
             }
-            SlashCmd::Save { save_slot: _ } => {
-                // TODO: save to save slot
+            SlashCmd::Time { source } => {
+                *settings.time_source = *source;
+                result_ev.write(CommandResultEvent::success(match source {
+                    TimeSource::RealTime => "Timed effects now run out in real time.",
+                    TimeSource::Turns => "Timed effects now run out turn by turn.",
+                }));
             }
             SlashCmd::View { screen } => {
                 view_ev.write(ChangeScreen {
@@ -0,0 +1,141 @@
+use crate::commands::commands::{GameCmd, GameCmdName, HelpTopic, SlashCmd, SlashCmdName};
+use clap::{Command, CommandFactory};
+
+/// the commands and the enums holding their aliases. game commands keep some of their aliases on
+/// `GameCmdName`, so both are looked at.
+fn roots(slash: bool) -> [Command; 2] {
+    if slash {
+        [SlashCmd::command(), SlashCmdName::command()]
+    } else {
+        [GameCmd::command(), GameCmdName::command()]
+    }
+}
+
+/// the sub commands of `cmd`, minus the "help" one clap adds on its own.
+fn subcommands(cmd: &Command) -> impl Iterator<Item = &Command> {
+    cmd.get_subcommands().filter(|sub| sub.get_name() != "help")
+}
+
+/// every other name the command can be typed as.
+fn aliases(roots: &[Command; 2], name: &str) -> Vec<String> {
+    let mut aliases: Vec<String> = roots
+        .iter()
+        .filter_map(|root| root.find_subcommand(name))
+        .flat_map(|cmd| cmd.get_all_aliases().map(String::from).collect::<Vec<_>>())
+        .collect();
+    aliases.sort();
+    aliases.dedup();
+
+    aliases
+}
+
+/// one line for each command, with its aliases & what it does.
+fn summaries(roots: &[Command; 2]) -> Vec<String> {
+    subcommands(&roots[0])
+        .map(|cmd| {
+            let aliases = aliases(roots, cmd.get_name());
+            let name = if aliases.is_empty() {
+                cmd.get_name().to_string()
+            } else {
+                format!("{} ({})", cmd.get_name(), aliases.join(", "))
+            };
+            let about = cmd.get_about().map(|about| about.to_string());
+
+            format!("  {name: <32}{}", about.unwrap_or_default())
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// what `/help` on its own shows, every command there is.
+pub fn help_index() -> String {
+    let mut lines = vec!["Commands:".to_string()];
+    lines.extend(summaries(&roots(false)));
+    lines.push(String::new());
+    lines.push("Slash commands:".into());
+    lines.extend(summaries(&roots(true)));
+    lines.push(String::new());
+    lines.push("Type \"/help <command>\" to learn more about a command.".into());
+
+    lines.join("\n")
+}
+
+/// how to use a command, pulled from its clap definition. `long_help` is the (optional) extra text
+/// from the help flavor assets.
+pub fn help_for(topic: HelpTopic, long_help: Option<&str>) -> String {
+    let name = topic.name();
+    let roots = roots(matches!(topic, HelpTopic::Slash(_)));
+
+    let Some(cmd) = roots[0].find_subcommand(&name) else {
+        return format!("There is no help for \"{name}\" yet.");
+    };
+
+    let mut lines = vec![match cmd.get_about() {
+        Some(about) => format!("{name}: {about}"),
+        None => name.clone(),
+    }];
+
+    lines.push(cmd.clone().render_usage().to_string());
+
+    let choices: Vec<&str> = subcommands(cmd).map(|sub| sub.get_name()).collect();
+
+    if !choices.is_empty() {
+        lines.push(format!("Choices: {}", choices.join(", ")));
+    }
+
+    let aliases = aliases(&roots, &name);
+
+    if !aliases.is_empty() {
+        lines.push(format!("Aliases: {}", aliases.join(", ")));
+    }
+
+    if let Some(examples) = cmd.get_after_help() {
+        lines.push("Examples:".into());
+        lines.extend(
+            examples
+                .to_string()
+                .lines()
+                .map(|example| format!("  {example}")),
+        );
+    }
+
+    if let Some(long_help) = long_help {
+        lines.push(String::new());
+        lines.push(long_help.trim().to_string());
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{help_for, help_index};
+    use crate::commands::commands::{GameCmdName, HelpTopic, SlashCmdName};
+
+    #[test]
+    fn help_comes_from_the_command_definitions() {
+        let index = help_index();
+        assert!(index.contains("equip"), "{index}");
+        assert!(index.contains("/save"), "{index}");
+        assert!(
+            !index.contains("  help"),
+            "clap's own help command is left out"
+        );
+
+        let go = help_for(HelpTopic::Game(GameCmdName::Go), None);
+        assert!(go.contains("Usage: go"), "{go}");
+        assert!(
+            go.contains("walk"),
+            "aliases from GameCmdName are listed: {go}"
+        );
+        assert!(go.contains("  go north"), "{go}");
+
+        let save = help_for(
+            HelpTopic::Slash(SlashCmdName::Save),
+            Some("saves are kept in a file."),
+        );
+        assert!(save.contains("Usage: /save <SAVE_SLOT>"), "{save}");
+        assert!(save.ends_with("saves are kept in a file."), "{save}");
+    }
+}
@@ -1,5 +1,9 @@
-use crate::HashMap;
-use bevy::prelude::*;
+use crate::{HashMap, ailments::StatusAilment};
+use bevy::{
+    color::palettes::tailwind::{AMBER_500, GREEN_400, ORANGE_400, PURPLE_400, SKY_400},
+    ecs::system::SystemParam,
+    prelude::*,
+};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumDiscriminants, EnumString};
 
@@ -12,13 +16,13 @@ pub enum WeaponHands {
     TwoHands,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Amount {
     Fixed(i32),
     Percent(f32),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Stat {
     HP,
     Mana,
@@ -29,6 +33,18 @@ pub enum Stat {
     Speed,
 }
 
+impl Stat {
+    pub const ALL: [Stat; 7] = [
+        Self::HP,
+        Self::Mana,
+        Self::Attack,
+        Self::Defence,
+        Self::MagicAtk,
+        Self::MagicDef,
+        Self::Speed,
+    ];
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConsumableEffect {
     Heal {
@@ -48,11 +64,18 @@ pub enum ConsumableEffect {
     //     /// the spell to cast
     //     spell: SpellId
     // },
+    /// afflicts whoever uses the item, ie. a bad mushroom.
     InflictStatus {
-        // ailment: StatusAilment,
-        // duration: f32,
-        // chance: f32,
+        ailment: StatusAilment,
+        /// how long (in seconds) the ailment lasts.
+        duration: f32,
+        /// the chance (0.0 - 1.0) the ailment takes hold.
+        chance: f32,
     },
+    /// removes ailments. an empty list cures every ailment.
+    Cure { ailments: Vec<StatusAilment> },
+    /// restores durability to the players equipped gear.
+    Repair { amount: u32 },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,10 +93,13 @@ pub enum EquipmentEffect {
         /// how long (in seconds) to apply this buf for.
         duration: f32,
     },
+    /// has a chance to afflict whatever the weapon hits.
     InflictStatus {
-        // ailment: StatusAilment,
-        // duration: f32,
-        // chance: f32,
+        ailment: StatusAilment,
+        /// how long (in seconds, or rounds in a fight) the ailment lasts.
+        duration: f32,
+        /// the chance (0.0 - 1.0) of the ailment taking hold on each hit.
+        chance: f32,
     },
 }
 
@@ -95,8 +121,89 @@ pub enum ItemType {
     KeyItem { effects: Vec<ConsumableEffect> },
 }
 
+impl ItemType {
+    /// true for items that can be wielded or worn.
+    pub fn is_equipment(&self) -> bool {
+        matches!(self, Self::Weapon { .. } | Self::Armor { .. })
+    }
+}
+
 // impl From<ItemType> for ItemTypeName {}
 
+impl std::fmt::Display for WeaponHands {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MainHand => write!(f, "Main Hand"),
+            Self::OffHand => write!(f, "Off Hand"),
+            Self::TwoHands => write!(f, "Two Handed"),
+        }
+    }
+}
+
+impl std::fmt::Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixed(value) => write!(f, "{value:+}"),
+            Self::Percent(value) => write!(f, "{:+.0}%", value * 100.0),
+        }
+    }
+}
+
+/// " over 10s", or nothing for instant effects.
+fn over(duration: f32) -> String {
+    if duration > 0.0 {
+        format!(" over {duration:.0}s")
+    } else {
+        String::new()
+    }
+}
+
+impl std::fmt::Display for ConsumableEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Heal { amount, duration } => write!(f, "{amount} HP{}", over(*duration)),
+            Self::Buf {
+                stat,
+                amount,
+                duration,
+            } => write!(f, "{amount} {stat:?} for {duration:.0}s"),
+            Self::InflictStatus {
+                ailment,
+                duration,
+                chance,
+            } => write!(
+                f,
+                "{:.0}% chance of {ailment} for {duration:.0}s",
+                chance * 100.0
+            ),
+            Self::Cure { ailments } if ailments.is_empty() => write!(f, "cures every ailment"),
+            Self::Cure { ailments } => {
+                let ailments: Vec<String> = ailments.iter().map(|a| a.to_string()).collect();
+                write!(f, "cures {}", ailments.join(", "))
+            }
+            Self::Repair { amount } => write!(f, "repairs gear by {amount}"),
+        }
+    }
+}
+
+impl std::fmt::Display for EquipmentEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HealUser { amount, duration } => write!(f, "{amount} HP{}", over(*duration)),
+            Self::BufUser { stat, amount, .. } => write!(f, "{amount} {stat:?}"),
+            Self::InflictStatus {
+                ailment,
+                duration,
+                chance,
+            } => write!(
+                f,
+                "{:.0}% chance to inflict {ailment} for {duration:.0}s",
+                chance * 100.0
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Asset, TypePath)]
 pub struct ItemAsset {
     /// self explanatory (many items may have similar name)
@@ -105,14 +212,421 @@ pub struct ItemAsset {
     pub description: String,
     /// further explanations of the item
     pub examine: Option<String>,
-    ///
+    /// what kind of item this is & what it does
     pub item_data: ItemType,
+    /// how heavy one of the item is. counts against the players carry capacity.
+    #[serde(default)]
+    pub weight: f32,
+    /// what the item is worth, in coins.
+    #[serde(default)]
+    pub value: u32,
+    #[serde(default)]
+    pub rarity: Rarity,
+    /// how many hits the item takes before it breaks. None for items that don't wear down.
+    #[serde(default)]
+    pub durability: Option<u32>,
+    /// whether several of this item share one inventory entry. weapons & armor never stack, each
+    /// one is equipped & worn down on its own.
+    #[serde(default)]
+    pub stackable: bool,
+    /// how many fit in one stack, only used when stackable.
+    #[serde(default = "default_max_stack")]
+    pub max_stack: usize,
+}
+
+/// how hard an item is to come by.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Display,
+)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Rarity {
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Common => AMBER_500.into(),
+            Self::Uncommon => GREEN_400.into(),
+            Self::Rare => SKY_400.into(),
+            Self::Epic => PURPLE_400.into(),
+            Self::Legendary => ORANGE_400.into(),
+        }
+    }
+}
+
+fn default_max_stack() -> usize {
+    99
+}
+
+impl ItemAsset {
+    /// how many of this item one inventory entry can hold.
+    pub fn stack_limit(&self) -> usize {
+        if self.stackable && !self.item_data.is_equipment() {
+            self.max_stack.max(1)
+        } else {
+            1
+        }
+    }
+
+    /// a plain item with nothing but a name & what it is, for building items in tests.
+    #[cfg(test)]
+    pub fn test(name: &str, item_data: ItemType) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            examine: None,
+            item_data,
+            weight: 0.0,
+            value: 0,
+            rarity: Default::default(),
+            durability: None,
+            stackable: false,
+            max_stack: 1,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Resource)]
 pub struct Items(pub HashMap<ItemId, Handle<ItemAsset>>);
 
-#[derive(Debug, Clone, Default, Component)]
+/// looks up what an item is.
+#[derive(SystemParam)]
+pub struct ItemData<'w> {
+    pub assets: Res<'w, Assets<ItemAsset>>,
+    pub items: Res<'w, Items>,
+}
+
+impl ItemData<'_> {
+    /// the item, None if it hasn't loaded.
+    pub fn get(&self, item_id: &ItemId) -> Option<&ItemAsset> {
+        self.assets.get(self.items.0.get(item_id)?)
+    }
+
+    /// see `describe_items`.
+    pub fn describe<'a>(&self, item_ids: impl IntoIterator<Item = &'a ItemId>) -> Option<String> {
+        describe_items(item_ids, &self.assets, &self.items)
+    }
+}
+
+/// the stacks in the players inventory, along with what they are.
+#[derive(SystemParam)]
+pub struct InventoryItems<'w, 's> {
+    pub entries: Query<'w, 's, (Entity, &'static InventoryEntry)>,
+    pub items: ItemData<'w>,
+}
+
+impl InventoryItems<'_, '_> {
+    /// every stack whose item has loaded.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &InventoryEntry, &ItemAsset)> {
+        self.entries
+            .iter()
+            .filter_map(|(entity, entry)| Some((entity, entry, self.items.get(&entry.asset_path)?)))
+    }
+}
+
+/// lists the items by name, grouping duplicates. ie "Goblin Club (x3), Potion". returns None when
+/// there is nothing to list.
+pub fn describe_items<'a>(
+    item_ids: impl IntoIterator<Item = &'a ItemId>,
+    item_assets: &Assets<ItemAsset>,
+    items: &Items,
+) -> Option<String> {
+    let counted = count_each(item_ids.into_iter().filter_map(|item_id| {
+        item_assets
+            .get(items.0.get(item_id)?)
+            .map(|asset| asset.name.clone())
+    }));
+
+    if counted.is_empty() {
+        return None;
+    }
+
+    let listed: Vec<String> = counted
+        .into_iter()
+        .map(|(name, count)| {
+            if count > 1 {
+                format!("{name} (x{count})")
+            } else {
+                name
+            }
+        })
+        .collect();
+
+    Some(listed.join(", "))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
 pub struct InventoryEntry {
     pub asset_path: ItemId,
+    /// how many of the item are in this stack. (always 1 for items that don't stack)
+    #[serde(default = "one")]
+    pub count: usize,
+    /// when the stack was last added to, higher is more recent. used to sort the inventory.
+    #[serde(default)]
+    pub acquired: u64,
+    /// the durability left on the item in use, the top of the stack. None when it's like new.
+    #[serde(default)]
+    pub durability: Option<u32>,
+}
+
+fn one() -> usize {
+    1
+}
+
+impl InventoryEntry {
+    pub fn new(asset_path: impl Into<ItemId>, count: usize) -> Self {
+        Self {
+            asset_path: asset_path.into(),
+            count,
+            acquired: 0,
+            durability: None,
+        }
+    }
+
+    /// how much durability the item in use has left, None for items that don't wear down.
+    pub fn durability_left(&self, asset: &ItemAsset) -> Option<u32> {
+        asset
+            .durability
+            .map(|max| self.durability.unwrap_or(max).min(max))
+    }
+}
+
+/// what happened to an item that was used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wear {
+    /// the item doesn't wear down.
+    Unworn,
+    /// the item has this much durability left.
+    Worn(u32),
+    /// the item broke. one is taken off the stack and the next one is like new.
+    Broke,
+}
+
+/// wears the item in use down by one.
+pub fn wear(entry: &InventoryEntry, asset: &ItemAsset) -> Wear {
+    match entry.durability_left(asset) {
+        None => Wear::Unworn,
+        Some(left) if left <= 1 => Wear::Broke,
+        Some(left) => Wear::Worn(left - 1),
+    }
+}
+
+/// the durability left after repairing by `amount`, None once it's like new again.
+pub fn repair(entry: &InventoryEntry, asset: &ItemAsset, amount: u32) -> Option<u32> {
+    let max = asset.durability?;
+    let repaired = entry.durability_left(asset)?.saturating_add(amount);
+
+    (repaired < max).then_some(repaired)
+}
+
+/// works out where `count` new items go given the sizes of the stacks already carried. existing
+/// stacks are topped up first, the rest is split into new stacks of at most `limit`. returns the
+/// new sizes of the existing stacks that changed (by index), and the sizes of the new stacks.
+pub fn plan_stacks(
+    existing: &[usize],
+    limit: usize,
+    mut count: usize,
+) -> (Vec<(usize, usize)>, Vec<usize>) {
+    let limit = limit.max(1);
+    let mut topped_up = Vec::new();
+
+    for (i, size) in existing.iter().enumerate() {
+        if count == 0 {
+            break;
+        }
+
+        let added = limit.saturating_sub(*size).min(count);
+
+        if added > 0 {
+            topped_up.push((i, size + added));
+            count -= added;
+        }
+    }
+
+    let mut new_stacks = Vec::new();
+
+    while count > 0 {
+        let size = count.min(limit);
+        new_stacks.push(size);
+        count -= size;
+    }
+
+    (topped_up, new_stacks)
+}
+
+/// adds `count` of an item to the inventory, filling up the stacks already carried before starting
+/// new ones. `durability` is what's left on each of the new stacks in order, any past the end are
+/// like new. call it once per item, the inventory passed in doesn't see the stacks it adds.
+pub fn add_to_inventory<'a>(
+    cmds: &mut Commands,
+    inventory: impl IntoIterator<Item = (Entity, &'a InventoryEntry)>,
+    item_id: &ItemId,
+    asset: &ItemAsset,
+    count: usize,
+    durability: &[Option<u32>],
+) {
+    let inventory: Vec<(Entity, &InventoryEntry)> = inventory.into_iter().collect();
+    let acquired = inventory
+        .iter()
+        .map(|(_, entry)| entry.acquired + 1)
+        .max()
+        .unwrap_or(1);
+    let stacks: Vec<(Entity, &InventoryEntry)> = inventory
+        .iter()
+        .filter(|(_, entry)| entry.asset_path == *item_id)
+        .copied()
+        .collect();
+    let sizes: Vec<usize> = stacks.iter().map(|(_, entry)| entry.count).collect();
+    let (topped_up, new_stacks) = plan_stacks(&sizes, asset.stack_limit(), count);
+
+    for (i, size) in topped_up {
+        let (entity, entry) = stacks[i];
+        cmds.entity(entity).insert(InventoryEntry {
+            count: size,
+            acquired,
+            ..entry.clone()
+        });
+    }
+
+    for (i, size) in new_stacks.into_iter().enumerate() {
+        cmds.spawn(InventoryEntry {
+            acquired,
+            durability: durability.get(i).copied().flatten(),
+            ..InventoryEntry::new(item_id.clone(), size)
+        });
+    }
+}
+
+/// splits stacks in to one `(thing, name)` pair per item, so picking by name can take part of a
+/// stack. ie. "drop 3 clubs" out of a stack of 5.
+pub fn one_per_item<T: Clone>(
+    stacks: impl IntoIterator<Item = (T, usize, String)>,
+) -> Vec<(T, String)> {
+    stacks
+        .into_iter()
+        .flat_map(|(thing, count, name)| std::iter::repeat_n((thing, name), count))
+        .collect()
+}
+
+/// groups things back together with how many times each came up, in the order they first did. ie.
+/// the stacks items were picked from & how many were picked from each.
+pub fn count_each<T: PartialEq>(things: impl IntoIterator<Item = T>) -> Vec<(T, usize)> {
+    let mut counted: Vec<(T, usize)> = Vec::new();
+
+    for thing in things {
+        match counted.iter_mut().find(|(counted, _)| *counted == thing) {
+            Some((_, count)) => *count += 1,
+            None => counted.push((thing, 1)),
+        }
+    }
+
+    counted
+}
+
+/// takes `count` items off a stack, despawning it once it's empty. returns true if the stack is
+/// gone.
+pub fn remove_from_stack(
+    cmds: &mut Commands,
+    entity: Entity,
+    entry: &InventoryEntry,
+    count: usize,
+) -> bool {
+    if count >= entry.count {
+        cmds.entity(entity).despawn();
+        true
+    } else {
+        cmds.entity(entity).insert(InventoryEntry {
+            count: entry.count - count,
+            ..entry.clone()
+        });
+        false
+    }
+}
+
+/// the repairs from using an item, added up.
+pub fn repair_amount(item: &ItemType) -> u32 {
+    let (ItemType::Consumable { effects } | ItemType::KeyItem { effects }) = item else {
+        return 0;
+    };
+
+    effects
+        .iter()
+        .map(|effect| match effect {
+            ConsumableEffect::Repair { amount } => *amount,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        InventoryEntry, ItemAsset, ItemType, Items, Wear, count_each, describe_items, one_per_item,
+        plan_stacks, repair, wear,
+    };
+    use bevy::asset::Assets;
+
+    #[test]
+    fn stacks_fill_up_before_splitting() {
+        assert_eq!(plan_stacks(&[], 1, 3), (vec![], vec![1, 1, 1]));
+        assert_eq!(plan_stacks(&[5], 99, 3), (vec![(0, 8)], vec![]));
+        assert_eq!(plan_stacks(&[10, 8], 10, 5), (vec![(1, 10)], vec![3]));
+        assert_eq!(plan_stacks(&[], 10, 25), (vec![], vec![10, 10, 5]));
+    }
+
+    #[test]
+    fn stacks_split_and_regroup() {
+        let split = one_per_item([(0, 2, "Club".to_string()), (1, 1, "Potion".to_string())]);
+
+        assert_eq!(split.len(), 3);
+        assert_eq!(split[1], (0, "Club".to_string()));
+        assert_eq!(
+            count_each(split.into_iter().map(|(stack, _)| stack)),
+            [(0, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn items_on_the_ground_are_grouped_by_name() {
+        let mut item_assets = Assets::<ItemAsset>::default();
+        let mut items = Items::default();
+
+        for (item_id, name) in [("club", "Club"), ("potion", "Potion")] {
+            let asset = ItemAsset::test(name, ItemType::KeyItem { effects: vec![] });
+            items.0.insert(item_id.into(), item_assets.add(asset));
+        }
+
+        let on_ground = ["club", "potion", "club", "missing"].map(String::from);
+        assert_eq!(
+            describe_items(&on_ground, &item_assets, &items).as_deref(),
+            Some("Club (x2), Potion")
+        );
+        assert_eq!(describe_items(&[], &item_assets, &items), None);
+    }
+
+    #[test]
+    fn items_wear_down_and_get_repaired() {
+        let club = ItemAsset {
+            durability: Some(3),
+            ..ItemAsset::test("Club", ItemType::KeyItem { effects: vec![] })
+        };
+        let mut entry = InventoryEntry::new("club", 1);
+
+        assert_eq!(wear(&entry, &club), Wear::Worn(2));
+        entry.durability = Some(1);
+        assert_eq!(wear(&entry, &club), Wear::Broke);
+        assert_eq!(repair(&entry, &club, 1), Some(2));
+        assert_eq!(repair(&entry, &club, 5), None, "fully repaired is like new");
+
+        let rock = ItemAsset {
+            durability: None,
+            ..club
+        };
+        assert_eq!(wear(&entry, &rock), Wear::Unworn);
+    }
 }
@@ -0,0 +1,194 @@
+use crate::{
+    Notification, NotificationLevel,
+    player::{BaseStats, CombatStats, Player},
+    state::MainState,
+    stats::update_effective_stats,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// where the level curve is loaded from (relative to "assets/").
+pub const LEVEL_CURVE: &str = "levels/player.levels.ron";
+
+/// how much XP each level takes & how the player grows with each one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Asset, TypePath)]
+pub struct LevelCurveAsset {
+    /// the total XP needed to reach each level after the first, in order. the length of this is
+    /// how many levels there are past level 1.
+    pub xp_needed: Vec<u32>,
+    /// what gets added to the players base stats every level past the first.
+    pub growth: CombatStats,
+}
+
+impl LevelCurveAsset {
+    /// the level the player is at with `xp` in total.
+    pub fn level_for(&self, xp: u32) -> u8 {
+        let passed = self
+            .xp_needed
+            .iter()
+            .take_while(|needed| xp >= **needed)
+            .count();
+
+        (passed + 1).min(u8::MAX as usize) as u8
+    }
+
+    /// the total XP needed to reach the level after `level`, None at the max level.
+    pub fn xp_for_next(&self, level: u8) -> Option<u32> {
+        self.xp_needed.get(level.max(1) as usize - 1).copied()
+    }
+
+    /// the players base stats at `level`.
+    pub fn stats_at(&self, base: &CombatStats, level: u8) -> CombatStats {
+        let levels = level.saturating_sub(1) as f32;
+
+        CombatStats {
+            hp: base.hp + self.growth.hp * levels,
+            mana: base.mana + self.growth.mana * levels,
+            attack: base.attack + self.growth.attack * levels,
+            defence: base.defence + self.growth.defence * levels,
+            magic_atk: base.magic_atk + self.growth.magic_atk * levels,
+            magic_def: base.magic_def + self.growth.magic_def * levels,
+            speed: base.speed + self.growth.speed * levels,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Resource)]
+pub struct LevelCurve(pub Handle<LevelCurveAsset>);
+
+/// how far the player has come.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Experience {
+    /// all the XP the player has ever earned.
+    pub xp: u32,
+    pub level: u8,
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self { xp: 0, level: 1 }
+    }
+}
+
+/// gives the player XP, ie. for beating a mob. anything else that rewards XP (like finishing a
+/// quest, once there are quests) sends this too.
+#[derive(Message, Debug, Clone)]
+pub struct GainXp {
+    pub amount: u32,
+    /// what the XP was for, ie. "defeating the goblin".
+    pub source: String,
+}
+
+/// sent for every level the player gains.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelUp(pub u8);
+
+#[derive(Clone, Debug)]
+pub struct LevelingPlugin;
+
+impl Plugin for LevelingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<GainXp>()
+            .add_message::<LevelUp>()
+            .init_resource::<LevelCurve>()
+            .add_systems(OnEnter(MainState::InGame), load_level_curve)
+            .add_systems(
+                Update,
+                (gain_xp, grow_stats.before(update_effective_stats))
+                    .chain()
+                    .run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+fn load_level_curve(mut curve: ResMut<LevelCurve>, asset_server: Res<AssetServer>) {
+    curve.0 = asset_server.load(LEVEL_CURVE);
+}
+
+/// adds up the XP the player earned, levelling them up when they pass the next threshold. XP
+/// earned before the curve has loaded still counts, the levels catch up once it's there.
+fn gain_xp(
+    mut gain_evs: MessageReader<GainXp>,
+    mut level_up_ev: MessageWriter<LevelUp>,
+    mut notification_ev: MessageWriter<Notification>,
+    mut player: Query<&mut Experience, With<Player>>,
+    curve: Res<LevelCurve>,
+    curves: Res<Assets<LevelCurveAsset>>,
+) {
+    let Ok(mut experience) = player.single_mut() else {
+        return;
+    };
+
+    for ev in gain_evs.read() {
+        experience.xp = experience.xp.saturating_add(ev.amount);
+        info!("gained {} xp for {}", ev.amount, ev.source);
+    }
+
+    let Some(curve) = curves.get(&curve.0) else {
+        return;
+    };
+    let level = curve.level_for(experience.xp);
+
+    if level <= experience.level {
+        return;
+    }
+
+    for reached in experience.level + 1..=level {
+        level_up_ev.write(LevelUp(reached));
+        notification_ev.write(Notification::new(
+            NotificationLevel::Alert,
+            format!("You reached level {reached}!"),
+        ));
+    }
+
+    experience.level = level;
+}
+
+/// keeps the players base stats in line with their level.
+fn grow_stats(
+    mut player: Query<(&Experience, &mut BaseStats), With<Player>>,
+    curve: Res<LevelCurve>,
+    curves: Res<Assets<LevelCurveAsset>>,
+) {
+    let (Ok((experience, mut base)), Some(curve)) = (player.single_mut(), curves.get(&curve.0))
+    else {
+        return;
+    };
+
+    let stats = curve.stats_at(&BaseStats::default().0, experience.level);
+    base.set_if_neq(BaseStats(stats));
+}
+
+#[cfg(test)]
+mod test {
+    use super::LevelCurveAsset;
+    use crate::player::CombatStats;
+
+    #[test]
+    fn levels_follow_the_curve() {
+        let curve = LevelCurveAsset {
+            xp_needed: vec![10, 30, 60],
+            growth: CombatStats {
+                hp: 5.0,
+                attack: 1.0,
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(curve.level_for(0), 1);
+        assert_eq!(curve.level_for(10), 2);
+        assert_eq!(curve.level_for(59), 3);
+        assert_eq!(curve.level_for(1000), 4, "the curve has a max level");
+        assert_eq!(curve.xp_for_next(1), Some(10));
+        assert_eq!(curve.xp_for_next(4), None);
+
+        let base = CombatStats {
+            hp: 30.0,
+            attack: 6.0,
+            ..Default::default()
+        };
+        assert_eq!(curve.stats_at(&base, 1), base);
+        assert_eq!(curve.stats_at(&base, 3).hp, 40.0);
+        assert_eq!(curve.stats_at(&base, 3).attack, 8.0);
+    }
+}
@@ -1,23 +1,48 @@
 use bevy::{prelude::*, window::WindowResized};
 use commands::commands::{Direction, ViewScreen};
 use fxhash::{FxHashMap, FxHashSet};
+use nouns::NounPhrase;
+use save::SaveSlot;
 use serde::{Deserialize, Serialize};
 use state::MainState;
 use std::time::Instant;
 use zones::{FlavorTextId, FlavorTextType};
 
+pub mod ailments;
+pub mod battle;
+pub mod carry;
 pub mod commands;
+pub mod effects;
+pub mod encounters;
+pub mod equipment;
+pub mod flavor;
+pub mod handle_bad_command;
 pub mod handle_exit_command;
 pub mod handle_game_cmd;
+pub mod handle_player_drop;
+pub mod handle_player_equip;
+pub mod handle_player_examine;
+pub mod handle_player_inventory;
 pub mod handle_player_look;
 pub mod handle_player_move;
+pub mod handle_player_use;
 pub mod handle_slash_cmd;
+pub mod help;
 pub mod items;
+pub mod leveling;
 pub mod menu_screens;
 pub mod mobs;
+pub mod notifications;
+pub mod nouns;
+pub mod player;
 pub mod player_take;
+pub mod save;
+pub mod shops;
 pub mod state;
+pub mod stats;
 pub mod ui;
+pub mod wallet;
+pub mod world;
 pub mod zones;
 
 pub type Hash = u64;
@@ -28,9 +53,23 @@ pub type CommandResponseType = Result<GenerincFlavorText, GenerincFlavorText>;
 #[derive(Debug, Clone, Message)]
 pub struct CommandEntered(pub String);
 
+/// how a command went, shown to the player in the look section. errors are styled differently
+/// from successes.
 #[derive(Debug, Clone, Message)]
 pub struct CommandResultEvent(pub CommandResponseType);
 
+impl CommandResultEvent {
+    /// the command did what it was asked to.
+    pub fn success(text: impl Into<String>) -> Self {
+        Self(Ok(GenerincFlavorText::Message(text.into())))
+    }
+
+    /// the command couldn't be done, `text` tells the player why.
+    pub fn failure(text: impl Into<String>) -> Self {
+        Self(Err(GenerincFlavorText::Message(text.into())))
+    }
+}
+
 #[derive(Message)]
 pub struct PlayerMovement(pub Direction);
 
@@ -46,15 +85,48 @@ pub struct ExitGame;
 #[derive(Message, Default)]
 pub struct NewZone;
 
+#[derive(Message)]
+pub struct PlayerTake(pub NounPhrase);
+
+#[derive(Message)]
+pub struct PlayerDrop(pub NounPhrase);
+
+#[derive(Message, Default)]
+pub struct PlayerInventory;
+
+#[derive(Message)]
+pub struct PlayerExamine(pub NounPhrase);
+
+#[derive(Message)]
+pub struct PlayerUse(pub NounPhrase);
+
+#[derive(Message)]
+pub struct PlayerEquip(pub NounPhrase);
+
+#[derive(Message)]
+pub struct PlayerUnequip(pub NounPhrase);
+
+/// sent every time the player changes something & so takes up a turn, ie. moving or using an item.
+/// looking around is free.
+#[derive(Message, Default)]
+pub struct TurnTaken;
+
+#[derive(Message)]
+pub struct SaveGame(pub SaveSlot);
+
+#[derive(Message)]
+pub struct LoadGame(pub SaveSlot);
+
+/// asks for the game to be autosaved.
 #[derive(Message, Default)]
-pub struct PlayerTake;
+pub struct Autosave;
 
 #[derive(Message)]
 pub struct ChangeScreen {
     to_screen: ViewScreen,
 }
 
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NotificationLevel {
     Error,
     Alert,
@@ -68,6 +140,16 @@ pub struct Notification {
     pub mesg: String,
 }
 
+impl Notification {
+    pub fn new(level: NotificationLevel, mesg: impl Into<String>) -> Self {
+        Self {
+            level,
+            time_stamp: Instant::now(),
+            mesg: mesg.into(),
+        }
+    }
+}
+
 /// used by the server to instruct the client on what flavor text to show the player.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GenerincFlavorText {
@@ -0,0 +1,311 @@
+use super::{
+    InvNavDir, list::InventoryListing, navigate_inventory, search::not_searching, toggle_inventory,
+};
+use crate::{
+    CommandResultEvent, PlayerDrop, PlayerEquip, PlayerExamine, PlayerUnequip, PlayerUse,
+    equipment::{EquipSlot, Equipment, compare_equipment},
+    items::{InventoryItems, ItemAsset, ItemType},
+    nouns::NounPhrase,
+    player::{BaseStats, Player},
+    state::{GameState, MainScreenState},
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// what can be done to an item from the inventory screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemAction {
+    Use,
+    Equip,
+    Unequip,
+    Drop,
+    Examine,
+    /// shows how the players stats would change if the item was swapped in for what's equipped.
+    Compare,
+}
+
+impl std::fmt::Display for ItemAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Use => write!(f, "Use"),
+            Self::Equip => write!(f, "Equip"),
+            Self::Unequip => write!(f, "Unequip"),
+            Self::Drop => write!(f, "Drop"),
+            Self::Examine => write!(f, "Examine"),
+            Self::Compare => write!(f, "Compare with equipped"),
+        }
+    }
+}
+
+impl ItemAction {
+    /// the actions that make sense for an item.
+    pub fn for_item(item: &ItemType, equipped: bool) -> Vec<Self> {
+        match item {
+            ItemType::Consumable { .. } | ItemType::KeyItem { .. } => {
+                vec![Self::Use, Self::Drop, Self::Examine]
+            }
+            ItemType::Weapon { .. } | ItemType::Armor { .. } if equipped => {
+                vec![Self::Unequip, Self::Drop, Self::Examine]
+            }
+            ItemType::Weapon { .. } | ItemType::Armor { .. } => {
+                vec![Self::Equip, Self::Drop, Self::Examine, Self::Compare]
+            }
+        }
+    }
+
+    /// true for actions that change something & so use up a turn, the same as typing them would.
+    /// these can't be done from the inventory in a fight, the battle decides whose turn it is.
+    pub fn takes_turn(&self) -> bool {
+        matches!(self, Self::Use | Self::Equip | Self::Unequip | Self::Drop)
+    }
+}
+
+/// where the actions that are also commands get sent, & where the rest show what they found.
+#[derive(SystemParam)]
+pub struct ActionWriters<'w> {
+    result_ev: MessageWriter<'w, CommandResultEvent>,
+    use_ev: MessageWriter<'w, PlayerUse>,
+    equip_ev: MessageWriter<'w, PlayerEquip>,
+    unequip_ev: MessageWriter<'w, PlayerUnequip>,
+    drop_ev: MessageWriter<'w, PlayerDrop>,
+    examine_ev: MessageWriter<'w, PlayerExamine>,
+}
+
+impl ActionWriters<'_> {
+    /// shows what an action that isn't a command found, ie. a comparison.
+    fn show(&mut self, text: String) {
+        self.result_ev.write(CommandResultEvent::success(text));
+    }
+
+    /// does `action` to exactly the item in the menu, returns false if the action isn't a command.
+    fn send(&mut self, action: ItemAction, menu: &ActionMenu) -> bool {
+        let thing = NounPhrase::exact(menu.item, &menu.name);
+
+        match action {
+            ItemAction::Use => {
+                self.use_ev.write(PlayerUse(thing));
+            }
+            ItemAction::Equip => {
+                self.equip_ev.write(PlayerEquip(thing));
+            }
+            ItemAction::Unequip => {
+                self.unequip_ev.write(PlayerUnequip(thing));
+            }
+            ItemAction::Drop => {
+                self.drop_ev.write(PlayerDrop(thing));
+            }
+            ItemAction::Examine => {
+                self.examine_ev.write(PlayerExamine(thing));
+            }
+            ItemAction::Compare => return false,
+        }
+
+        true
+    }
+}
+
+/// the action menu of the highlighted item, opened with enter.
+#[derive(Debug, Clone)]
+pub struct ActionMenu {
+    pub item: Entity,
+    pub name: String,
+    pub actions: Vec<ItemAction>,
+    pub selected: usize,
+}
+
+impl ActionMenu {
+    pub fn lines(&self) -> String {
+        self.actions
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                let marker = if i == self.selected { ">" } else { " " };
+                format!("{marker} {action}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ItemMenu(pub Option<ActionMenu>);
+
+pub fn item_menu_closed(menu: Res<ItemMenu>) -> bool {
+    menu.0.is_none()
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemActionsPlugin;
+
+impl Plugin for ItemActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ItemMenu>()
+            .add_systems(
+                Update,
+                handle_item_menu
+                    .after(toggle_inventory)
+                    .after(navigate_inventory)
+                    .run_if(in_state(MainScreenState::Inventory))
+                    .run_if(not_searching),
+            )
+            .add_systems(OnExit(MainScreenState::Inventory), close_item_menu);
+    }
+}
+
+fn close_item_menu(mut menu: ResMut<ItemMenu>) {
+    menu.0 = None;
+}
+
+/// enter opens the menu for the highlighted item. while it's open, up & down pick an action, enter
+/// does it & escape closes the menu. only actions that don't take a turn are offered in a fight.
+pub fn handle_item_menu(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<ItemMenu>,
+    mut action_evs: ActionWriters,
+    inventory: InventoryItems,
+    player: Query<(&Equipment, &BaseStats), With<Player>>,
+    listing: InventoryListing,
+    game_state: Res<State<GameState>>,
+) {
+    let equipment = player.single().ok();
+
+    let Some(open) = &mut menu.0 else {
+        if keys.just_pressed(KeyCode::Enter)
+            && let Some((item, asset)) = listing.selected(&inventory)
+        {
+            let equipped = equipment.is_some_and(|(equipment, _)| equipment.is_equipped(item));
+            let mut actions = ItemAction::for_item(&asset.item_data, equipped);
+
+            if *game_state.get() == GameState::Battle {
+                actions.retain(|action| !action.takes_turn());
+            }
+
+            menu.0 = Some(ActionMenu {
+                item,
+                name: asset.name.clone(),
+                actions,
+                selected: 0,
+            });
+        }
+
+        return;
+    };
+
+    if keys.just_released(KeyCode::Escape) {
+        menu.0 = None;
+        return;
+    }
+
+    match InvNavDir::from_keys(&keys) {
+        Some(InvNavDir::Up) => open.selected = open.selected.saturating_sub(1),
+        Some(InvNavDir::Down) => open.selected = (open.selected + 1).min(open.actions.len() - 1),
+        None => {}
+    }
+
+    if !keys.just_pressed(KeyCode::Enter) {
+        return;
+    }
+
+    let action = open.actions[open.selected];
+
+    if !action_evs.send(action, open)
+        && let Some((equipment, base)) = equipment
+    {
+        let asset_of = |item: Entity| {
+            let entry = inventory.entries.get(item).ok()?.1;
+            inventory.items.get(&entry.asset_path)
+        };
+
+        if let Some(candidate) = asset_of(open.item) {
+            action_evs.show(compare_with_equipped(
+                candidate, open.item, equipment, base, asset_of,
+            ));
+        }
+    }
+
+    menu.0 = None;
+}
+
+/// describes how the players stats change if `candidate` replaced what's in the slots it takes up.
+fn compare_with_equipped<'a>(
+    candidate: &'a ItemAsset,
+    candidate_id: Entity,
+    equipment: &Equipment,
+    base: &BaseStats,
+    asset_of: impl Fn(Entity) -> Option<&'a ItemAsset>,
+) -> String {
+    let slots = EquipSlot::for_item(&candidate.item_data).unwrap_or(&[]);
+    let worn = equipment.items();
+    let displaced: Vec<Entity> = worn
+        .iter()
+        .copied()
+        .filter(|item| {
+            *item != candidate_id && slots.iter().any(|slot| equipment.get(*slot) == Some(*item))
+        })
+        .collect();
+
+    let now: Vec<&ItemType> = worn
+        .iter()
+        .filter_map(|item| asset_of(*item))
+        .map(|asset| &asset.item_data)
+        .collect();
+    let then: Vec<&ItemType> = worn
+        .iter()
+        .filter(|item| !displaced.contains(item))
+        .filter_map(|item| asset_of(*item))
+        .map(|asset| &asset.item_data)
+        .chain([&candidate.item_data])
+        .collect();
+
+    let replaced: Vec<String> = displaced
+        .iter()
+        .filter_map(|item| asset_of(*item))
+        .map(|asset| asset.name.clone())
+        .collect();
+    let replaced = if replaced.is_empty() {
+        "nothing".into()
+    } else {
+        replaced.join(" & ")
+    };
+
+    let changes = compare_equipment(&base.0, &now, &then);
+
+    if changes.is_empty() {
+        return format!(
+            "Swapping {} for {replaced} wouldn't change your stats.",
+            candidate.name
+        );
+    }
+
+    let mut lines = vec![format!("{} compared with {replaced}:", candidate.name)];
+    lines.extend(changes.into_iter().map(|(stat, now, then)| {
+        format!(
+            "  {: <10}{now: >6.1} -> {then: >6.1} ({:+.1})",
+            format!("{stat:?}"),
+            then - now
+        )
+    }));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::ItemAction;
+    use crate::items::{ItemType, WeaponHands};
+
+    #[test]
+    fn actions_depend_on_the_item() {
+        let potion = ItemType::Consumable { effects: vec![] };
+        let club = ItemType::Weapon {
+            hands: WeaponHands::MainHand,
+            effects: vec![],
+        };
+
+        assert_eq!(ItemAction::for_item(&potion, false)[0], ItemAction::Use);
+        assert!(ItemAction::for_item(&club, false).contains(&ItemAction::Compare));
+        assert_eq!(ItemAction::for_item(&club, true)[0], ItemAction::Unequip);
+        assert!(ItemAction::Drop.takes_turn());
+        assert!(!ItemAction::Examine.takes_turn());
+        assert!(!ItemAction::Compare.takes_turn());
+    }
+}
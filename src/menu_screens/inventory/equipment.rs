@@ -0,0 +1,98 @@
+use super::list::setup_item_list;
+use crate::{
+    equipment::{EquipSlot, Equipment},
+    items::{InventoryEntry, ItemAsset, Items, Stat},
+    player::{BaseStats, EffectiveStats, Player},
+    state::{InventoryState, MainScreenState},
+    ui::MainTextUiNode,
+};
+use bevy::{color::palettes::tailwind::AMBER_500, prelude::*};
+
+#[derive(Component)]
+pub struct EquipmentDisplayText;
+
+#[derive(Clone, Debug)]
+pub struct EquipmentPlugin;
+
+impl Plugin for EquipmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(InventoryState::Equipment),
+            setup_equipment_inventory_menu.after(setup_item_list),
+        )
+        .add_systems(
+            Update,
+            display_equipment
+                .run_if(in_state(MainScreenState::Inventory))
+                .run_if(in_state(InventoryState::Equipment)),
+        );
+    }
+}
+
+/// adds a summary of what's equipped under the list of equipment.
+pub fn setup_equipment_inventory_menu(
+    mut cmds: Commands,
+    asset_server: Res<AssetServer>,
+    main_screen: Query<Entity, With<MainTextUiNode>>,
+) {
+    let text_font = TextFont {
+        font: asset_server.load("fonts/AnonymousPro.ttf"),
+        ..default()
+    };
+
+    if let Ok(main_screen) = main_screen.single() {
+        cmds.entity(main_screen).with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                text_font.clone().with_font_size(30.0),
+                TextLayout::new(Justify::Left, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+                EquipmentDisplayText,
+            ));
+        });
+    }
+}
+
+/// lists what is in each slot, and how the equipment changes the players stats.
+pub fn display_equipment(
+    mut text: Query<&mut Text, With<EquipmentDisplayText>>,
+    player: Query<(&Equipment, &BaseStats, &EffectiveStats), With<Player>>,
+    inventory: Query<&InventoryEntry>,
+    item_assets: Res<Assets<ItemAsset>>,
+    items: Res<Items>,
+) {
+    let (Ok(mut text), Ok((equipment, base, effective))) = (text.single_mut(), player.single())
+    else {
+        return;
+    };
+
+    let slots = EquipSlot::ALL.map(|slot| {
+        let name = equipment
+            .get(slot)
+            .and_then(|item| inventory.get(item).ok())
+            .and_then(|entry| item_assets.get(items.0.get(&entry.asset_path)?))
+            .map(|asset| asset.name.as_str())
+            .unwrap_or("(empty)");
+
+        format!("{: <10} {name}", format!("{slot}:"))
+    });
+
+    let stats = Stat::ALL.map(|stat| {
+        let (base, effective) = (base.0.get(&stat), effective.0.get(&stat));
+
+        if base == effective {
+            format!("{: <10} {effective:.1}", format!("{stat:?}:"))
+        } else {
+            format!(
+                "{: <10} {effective:.1} (base {base:.1})",
+                format!("{stat:?}:")
+            )
+        }
+    });
+
+    let new_text = format!("{}\n\n{}", slots.join("\n"), stats.join("\n"));
+
+    if text.0 != new_text {
+        text.0 = new_text;
+    }
+}
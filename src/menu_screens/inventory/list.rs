@@ -0,0 +1,578 @@
+use super::{
+    InvIndex, InvNavDir, WINDOW_ROWS, actions::ItemMenu, default_clear_main_window, search,
+};
+use crate::{
+    carry::CarryLoad,
+    equipment::Equipment,
+    items::{InventoryItems, ItemAsset, ItemType, ItemTypeName},
+    player::Player,
+    state::{InventoryState, MainScreenState},
+    ui::{MainTextUiNode, update::show_command_results},
+};
+use bevy::{color::palettes::tailwind::AMBER_500, ecs::system::SystemParam, prelude::*};
+
+#[derive(Component)]
+pub struct ItemDisplayText;
+
+#[derive(Component, Debug)]
+pub struct InventoryOrder(pub usize);
+
+#[derive(Component)]
+pub struct InventoryViewText;
+
+/// how the last thing done from the item menu went, under the list.
+#[derive(Component)]
+pub struct ItemResultText;
+
+/// the orders the inventory can be listed in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortMode {
+    #[default]
+    Name,
+    Type,
+    /// the most recently picked up first.
+    Recent,
+    /// the most valuable first.
+    Value,
+}
+
+impl SortMode {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Name => Self::Type,
+            Self::Type => Self::Recent,
+            Self::Recent => Self::Value,
+            Self::Value => Self::Name,
+        }
+    }
+}
+
+impl std::fmt::Display for SortMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name => write!(f, "name"),
+            Self::Type => write!(f, "type"),
+            Self::Recent => write!(f, "recent"),
+            Self::Value => write!(f, "value"),
+        }
+    }
+}
+
+/// how the player wants the inventory listed. kept between visits to the inventory screen.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct InventoryView {
+    pub sort: SortMode,
+    /// only items with this in their name are listed.
+    pub search: String,
+    /// true while the player is typing in to the search box.
+    pub editing: bool,
+}
+
+impl InventoryView {
+    /// true if the item should be listed.
+    pub fn matches(&self, item: &ItemAsset) -> bool {
+        item.name
+            .to_lowercase()
+            .contains(&self.search.trim().to_lowercase())
+    }
+
+    /// sorts `(thing, acquired, item)` tuples, ties are broken by name.
+    pub fn sort<T>(&self, listed: &mut [(T, u64, ItemAsset)]) {
+        listed.sort_by(|(_, a_acquired, a), (_, b_acquired, b)| {
+            let by_mode = match self.sort {
+                SortMode::Name => std::cmp::Ordering::Equal,
+                SortMode::Type => {
+                    ItemTypeName::from(&a.item_data).cmp(&ItemTypeName::from(&b.item_data))
+                }
+                SortMode::Recent => b_acquired.cmp(a_acquired),
+                SortMode::Value => b.value.cmp(&a.value),
+            };
+
+            by_mode.then_with(|| a.name.cmp(&b.name))
+        });
+    }
+}
+
+/// what the inventory screen is listing, & which item is highlighted.
+#[derive(SystemParam)]
+pub struct InventoryListing<'w> {
+    tab: Res<'w, State<InventoryState>>,
+    view: Res<'w, InventoryView>,
+    inv_index: Res<'w, InvIndex>,
+}
+
+impl InventoryListing<'_> {
+    pub fn tab(&self) -> InventoryState {
+        *self.tab.get()
+    }
+
+    /// see `get_inventory_ordered`.
+    pub fn items(&self, inventory: &InventoryItems) -> Vec<(Entity, ItemAsset)> {
+        get_inventory_ordered(inventory, self.tab(), &self.view)
+    }
+
+    pub fn selected(&self, inventory: &InventoryItems) -> Option<(Entity, ItemAsset)> {
+        self.items(inventory).into_iter().nth(self.inv_index.0)
+    }
+}
+
+/// what the item list is drawn with.
+#[derive(SystemParam)]
+pub struct ItemListUi<'w, 's> {
+    asset_server: Res<'w, AssetServer>,
+    text_q: Query<'w, 's, (Entity, &'static InventoryOrder), With<ItemDisplayText>>,
+    title: Query<'w, 's, &'static mut Text, With<InventoryViewText>>,
+}
+
+/// every inventory tab is the same scrolling list of items, filtered down to one type of item.
+#[derive(Clone, Debug)]
+pub struct ItemListPlugin;
+
+impl Plugin for ItemListPlugin {
+    fn build(&self, app: &mut App) {
+        for tab in TABS {
+            app.add_systems(OnEnter(tab), setup_item_list)
+                .add_systems(OnExit(tab), default_clear_main_window);
+        }
+
+        app.add_systems(
+            Update,
+            (
+                (navigate_inventory, display_items)
+                    .chain()
+                    .after(search::search_inventory),
+                show_command_results::<ItemResultText>,
+            )
+                .run_if(in_state(MainScreenState::Inventory)),
+        );
+    }
+}
+
+const TABS: [InventoryState; 5] = [
+    InventoryState::All,
+    InventoryState::Consumables,
+    InventoryState::Weapons,
+    InventoryState::Equipment,
+    InventoryState::KeyItems,
+];
+
+/// the types of item a tab lists, None lists everything. equipment is anything that can be worn.
+pub fn listed_types(tab: InventoryState) -> Option<&'static [ItemTypeName]> {
+    match tab {
+        InventoryState::All => None,
+        InventoryState::Consumables => Some(&[ItemTypeName::Consumable]),
+        InventoryState::Weapons => Some(&[ItemTypeName::Weapon]),
+        InventoryState::Equipment => Some(&[ItemTypeName::Weapon, ItemTypeName::Armor]),
+        InventoryState::KeyItems => Some(&[ItemTypeName::KeyItem]),
+    }
+}
+
+fn tab_title(tab: InventoryState) -> &'static str {
+    match tab {
+        InventoryState::All => "All Items",
+        InventoryState::Consumables => "Consumables",
+        InventoryState::Weapons => "Weapons",
+        InventoryState::Equipment => "Equipment",
+        InventoryState::KeyItems => "Key Items",
+    }
+}
+
+fn navigate_inventory(
+    mut nav_evs: MessageReader<InvNavDir>,
+    inventory: InventoryItems,
+    tab: Res<State<InventoryState>>,
+    view: Res<InventoryView>,
+    mut inv_index: ResMut<InvIndex>,
+) {
+    let inv_size = get_inventory_ordered(&inventory, *tab.get(), &view).len();
+
+    // the selection can point past the end after using or dropping the last item.
+    if inv_index.0 >= inv_size {
+        inv_index.clamp(inv_size);
+    }
+
+    for ev in nav_evs.read() {
+        inv_index.step(ev, inv_size);
+    }
+}
+
+pub fn setup_item_list(
+    mut cmds: Commands,
+    asset_server: Res<AssetServer>,
+    main_screen: Query<Entity, With<MainTextUiNode>>,
+    tab: Res<State<InventoryState>>,
+) {
+    let text_font = TextFont {
+        font: asset_server.load("fonts/AnonymousPro.ttf"),
+        ..default()
+    };
+
+    if let Ok(main_screen) = main_screen.single() {
+        cmds.entity(main_screen).with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Inventory => {}", tab_title(*tab.get()))),
+                text_font.clone().with_font_size(60.0),
+                TextLayout::new(Justify::Center, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+            ));
+            parent.spawn((
+                Text::new(""),
+                text_font.clone().with_font_size(30.0),
+                TextLayout::new(Justify::Center, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+                InventoryViewText,
+            ));
+
+            for i in 0..WINDOW_ROWS {
+                parent.spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::SpaceEvenly,
+                        ..default()
+                    },
+                    ItemDisplayText,
+                    InventoryOrder(i),
+                ));
+            }
+
+            parent.spawn((
+                Text::new(""),
+                text_font.clone().with_font_size(30.0),
+                TextLayout::new(Justify::Left, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+                ItemResultText,
+            ));
+        });
+    }
+}
+
+/// the items listed on `tab` that match the search, in the order the player picked.
+pub fn get_inventory_ordered(
+    inventory: &InventoryItems,
+    tab: InventoryState,
+    view: &InventoryView,
+) -> Vec<(Entity, ItemAsset)> {
+    let listed = listed_types(tab);
+    let mut filtered: Vec<(Entity, u64, ItemAsset)> = inventory
+        .iter()
+        .filter(|(_, _, asset)| {
+            listed.is_none_or(|listed| listed.contains(&ItemTypeName::from(&asset.item_data)))
+                && view.matches(asset)
+        })
+        .map(|(entity, entry, asset)| (entity, entry.acquired, asset.clone()))
+        .collect();
+    view.sort(&mut filtered);
+
+    filtered
+        .into_iter()
+        .map(|(entity, _, asset)| (entity, asset))
+        .collect()
+}
+
+/// the short column shown next to an item's name.
+pub fn item_tag(item: &ItemAsset, tab: InventoryState, equipped: bool) -> String {
+    let equipped = if equipped { " (equipped)" } else { "" };
+
+    match (&item.item_data, tab) {
+        (_, InventoryState::All) => format!("{}", ItemTypeName::from(&item.item_data)),
+        (ItemType::Weapon { hands, .. }, _) => format!("{hands}{equipped}"),
+        (ItemType::Armor { .. }, _) if !equipped.is_empty() => "equipped".into(),
+        (ItemType::Consumable { effects } | ItemType::KeyItem { effects }, _) => {
+            format!("{} effect(s)", effects.len())
+        }
+        _ => String::new(),
+    }
+}
+
+/// what is shown under the selected item. the all items tab sticks to the description & worth, the
+/// others also list what the item does. `durability` is what's left on the item in use.
+pub fn item_details(
+    item: &ItemAsset,
+    tab: InventoryState,
+    equipped: bool,
+    durability: Option<u32>,
+) -> String {
+    let mut lines = vec![item.description.clone()];
+    let mut worth = format!("{}, worth {} coins", item.rarity, item.value);
+
+    if let (Some(left), Some(max)) = (durability, item.durability) {
+        worth = format!("{worth}, durability {left}/{max}");
+    }
+
+    if tab == InventoryState::All {
+        lines.push(worth);
+        return lines.join("\n");
+    }
+
+    let effects: Vec<String> = match &item.item_data {
+        ItemType::Consumable { effects } | ItemType::KeyItem { effects } => {
+            effects.iter().map(|effect| effect.to_string()).collect()
+        }
+        ItemType::Weapon { hands, effects } => {
+            lines.push(format!("Hands: {hands}"));
+            effects.iter().map(|effect| effect.to_string()).collect()
+        }
+        ItemType::Armor { effects } => effects.iter().map(|effect| effect.to_string()).collect(),
+    };
+
+    if effects.is_empty() {
+        lines.push("Effects: none".into());
+    } else {
+        lines.push(format!("Effects: {}", effects.join(", ")));
+    }
+
+    if item.item_data.is_equipment() {
+        lines.push(if equipped {
+            "Equipped".into()
+        } else {
+            "Not equipped".into()
+        });
+    }
+
+    lines.push(worth);
+    lines.join("\n")
+}
+
+pub fn display_items(
+    mut cmds: Commands,
+    ui: ItemListUi,
+    inventory: InventoryItems,
+    equipment: Query<&Equipment, With<Player>>,
+    listing: InventoryListing,
+    menu: Res<ItemMenu>,
+    load: CarryLoad,
+) {
+    let ItemListUi {
+        asset_server,
+        text_q,
+        mut title,
+    } = ui;
+    let (view, inv_index) = (&listing.view, &listing.inv_index);
+    let text_font = TextFont {
+        font: asset_server.load("fonts/AnonymousPro.ttf"),
+        ..default()
+    };
+
+    let tab = listing.tab();
+    let mut text_nodes: Vec<_> = text_q.iter().collect();
+    text_nodes.sort_by_key(|(_text, inv_ord)| inv_ord.0);
+    let inv = listing.items(&inventory);
+
+    if let Ok(mut title) = title.single_mut() {
+        let cursor = if view.editing { "_" } else { "" };
+        let new_title = format!(
+            "load: {:.1}/{:.1}   sort: {} [tab]   search: {}{cursor} [/]",
+            load.weight(),
+            load.capacity(),
+            view.sort,
+            view.search
+        );
+        title.set_if_neq(Text(new_title));
+    }
+    let equipment = equipment.single().ok();
+
+    for (node, _) in text_nodes.iter() {
+        cmds.entity(*node).despawn_children();
+    }
+
+    if inv.is_empty() {
+        if let Some((node, _)) = text_nodes.first() {
+            cmds.entity(*node).with_children(|parent| {
+                parent.spawn((
+                    Text::new(if !view.search.is_empty() {
+                        format!("Nothing matches \"{}\"", view.search)
+                    } else if tab == InventoryState::All {
+                        "Inventory Empty".into()
+                    } else {
+                        format!("No {}", tab_title(tab))
+                    }),
+                    text_font.clone().with_font_size(30.0),
+                    TextLayout::new(Justify::Left, LineBreak::WordBoundary),
+                    TextColor(AMBER_500.into()),
+                    ItemDisplayText,
+                ));
+            });
+        }
+
+        return;
+    }
+
+    for (i, (node, _)) in text_nodes.iter().enumerate() {
+        let Some((entity, item)) = inv.get(i + inv_index.1) else {
+            break;
+        };
+        let selected = i + inv_index.1 == inv_index.0;
+        let equipped = equipment.is_some_and(|equipment| equipment.is_equipped(*entity));
+        let entry = inventory.entries.get(*entity).ok().map(|(_, entry)| entry);
+        let details = item_details(
+            item,
+            tab,
+            equipped,
+            entry.and_then(|entry| entry.durability_left(item)),
+        );
+
+        let row = cmds
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceEvenly,
+                ..default()
+            })
+            .id();
+
+        let intermediate = cmds
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::SpaceEvenly,
+                ..default()
+            })
+            .add_child(row)
+            .id();
+
+        if selected {
+            cmds.entity(intermediate).insert(Outline {
+                width: Val::Px(5.),
+                offset: Val::Px(0.0),
+                color: AMBER_500.into(),
+            });
+        }
+
+        cmds.entity(*node).add_child(intermediate);
+
+        cmds.entity(row).with_children(|parent| {
+            for (column, color) in [
+                (format!("#{: <3} =>", i + 1 + inv_index.1), AMBER_500.into()),
+                (
+                    match entry.map(|entry| entry.count) {
+                        Some(count) if count > 1 => format!("{} (x{count})", item.name),
+                        _ => item.name.clone(),
+                    },
+                    AMBER_500.into(),
+                ),
+                (format!("[{}]", item.rarity), item.rarity.color()),
+                (item_tag(item, tab, equipped), AMBER_500.into()),
+            ] {
+                parent.spawn((
+                    Text::new(column),
+                    text_font.clone().with_font_size(30.0),
+                    TextLayout::new(Justify::Left, LineBreak::WordBoundary),
+                    TextColor(color),
+                    ItemDisplayText,
+                ));
+            }
+        });
+
+        if selected {
+            cmds.entity(intermediate).with_children(|parent| {
+                parent.spawn((
+                    Text::new(match &menu.0 {
+                        Some(open) if open.item == *entity => {
+                            format!("{details}\n\n{}", open.lines())
+                        }
+                        _ => details,
+                    }),
+                    text_font.clone().with_font_size(30.0),
+                    TextLayout::new(Justify::Center, LineBreak::WordBoundary),
+                    TextColor(AMBER_500.into()),
+                    ItemDisplayText,
+                ));
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InventoryView, SortMode, item_details, item_tag, listed_types};
+    use crate::{
+        items::{EquipmentEffect, ItemAsset, ItemType, ItemTypeName, Rarity, Stat, WeaponHands},
+        state::InventoryState,
+    };
+
+    #[test]
+    fn tabs_show_type_specific_details() {
+        let club = ItemAsset {
+            description: "A big stick.".into(),
+            rarity: Rarity::Rare,
+            durability: Some(30),
+            ..ItemAsset::test(
+                "Club",
+                ItemType::Weapon {
+                    hands: WeaponHands::TwoHands,
+                    effects: vec![EquipmentEffect::BufUser {
+                        stat: Stat::Attack,
+                        amount: crate::items::Amount::Fixed(3),
+                        duration: 0.0,
+                    }],
+                },
+            )
+        };
+
+        assert_eq!(
+            listed_types(InventoryState::Weapons),
+            Some(&[ItemTypeName::Weapon][..])
+        );
+        assert!(
+            listed_types(InventoryState::Equipment)
+                .is_some_and(|listed| listed.contains(&ItemTypeName::Weapon))
+        );
+        assert_eq!(listed_types(InventoryState::All), None);
+        assert_eq!(
+            item_details(&club, InventoryState::All, true, None),
+            "A big stick.\nRare, worth 0 coins"
+        );
+        assert_eq!(
+            item_details(&club, InventoryState::Weapons, true, Some(12)),
+            "A big stick.\nHands: Two Handed\nEffects: +3 Attack\nEquipped\nRare, worth 0 coins, durability 12/30"
+        );
+        assert_eq!(
+            item_tag(&club, InventoryState::Weapons, false),
+            "Two Handed"
+        );
+    }
+
+    #[test]
+    fn sorting_and_searching() {
+        let item = |name: &str, value, item_data| ItemAsset {
+            value,
+            ..ItemAsset::test(name, item_data)
+        };
+        let potion = || ItemType::Consumable { effects: vec![] };
+        let mut listed = vec![
+            ("a", 3, item("Potion", 15, potion())),
+            ("b", 1, item("Antidote", 12, potion())),
+            (
+                "c",
+                2,
+                item(
+                    "Club",
+                    8,
+                    ItemType::Weapon {
+                        hands: WeaponHands::MainHand,
+                        effects: vec![],
+                    },
+                ),
+            ),
+        ];
+        let order = |listed: &[(&str, u64, ItemAsset)]| {
+            listed.iter().map(|(id, _, _)| *id).collect::<String>()
+        };
+
+        let mut view = InventoryView::default();
+        view.sort(&mut listed);
+        assert_eq!(order(&listed), "bca");
+
+        for (sort, expected) in [
+            (SortMode::Type, "bac"),
+            (SortMode::Recent, "acb"),
+            (SortMode::Value, "abc"),
+        ] {
+            view.sort = sort;
+            view.sort(&mut listed);
+            assert_eq!(order(&listed), expected, "sorting by {sort}");
+        }
+
+        view.search = "CL".into();
+        assert!(view.matches(&listed[2].2));
+        assert!(!view.matches(&listed[0].2));
+    }
+}
@@ -2,7 +2,10 @@ use super::{default_clear_main_window, disable_cmd_prompt};
 use crate::state::{InventoryState, MainScreenState};
 use bevy::prelude::*;
 
-pub mod all;
+pub mod actions;
+pub mod equipment;
+pub mod list;
+pub mod search;
 
 #[derive(Message)]
 pub enum InvNavDir {
@@ -12,10 +15,56 @@ pub enum InvNavDir {
     // NextScreen,
 }
 
+impl InvNavDir {
+    /// reads the up & down arrows, shared by every screen that scrolls through a list.
+    pub fn from_keys(keys: &ButtonInput<KeyCode>) -> Option<Self> {
+        if keys.just_released(KeyCode::ArrowUp) {
+            Some(Self::Up)
+        } else if keys.just_released(KeyCode::ArrowDown) {
+            Some(Self::Down)
+        } else {
+            None
+        }
+    }
+}
+
+/// how many rows a scrolling list shows at once.
+pub const WINDOW_ROWS: usize = 18;
+
 #[derive(Resource, Debug, Default, Clone, Copy)]
 pub struct InvIndex(pub usize, pub usize); // .0 is the index of the selected item, and .1 is the
 // start window, index
 
+impl InvIndex {
+    /// moves the selection one row in `dir` through a list of `len` entries, scrolling the window
+    /// when the selection gets near its edge.
+    pub fn step(&mut self, dir: &InvNavDir, len: usize) {
+        match dir {
+            InvNavDir::Up if self.0 > 0 => {
+                self.0 -= 1;
+
+                if self.1 + 1 > self.0 && self.1 > 0 {
+                    self.1 -= 1;
+                }
+            }
+            InvNavDir::Down if self.0 + 1 < len => {
+                self.0 += 1;
+
+                if self.0 + 1 >= self.1 + WINDOW_ROWS {
+                    self.1 += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// pulls the selection back inside a list that shrank to `len` entries.
+    pub fn clamp(&mut self, len: usize) {
+        self.0 = self.0.min(len.saturating_sub(1));
+        self.1 = self.1.min(self.0);
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct InventoryPlugin;
 
@@ -23,11 +72,19 @@ impl Plugin for InventoryPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<InvNavDir>()
             .init_resource::<InvIndex>()
-            .add_plugins((all::AllItemsPlugin,))
+            .add_plugins((
+                list::ItemListPlugin,
+                equipment::EquipmentPlugin,
+                actions::ItemActionsPlugin,
+                search::InventorySearchPlugin,
+            ))
             .add_systems(OnEnter(MainScreenState::Inventory), disable_cmd_prompt)
             .add_systems(
                 Update,
-                (toggle_inventory, navigate_inventory).run_if(in_state(MainScreenState::Inventory)),
+                (toggle_inventory, navigate_inventory)
+                    .run_if(in_state(MainScreenState::Inventory))
+                    .run_if(actions::item_menu_closed)
+                    .run_if(search::not_searching),
             )
             .add_systems(
                 OnExit(MainScreenState::Inventory),
@@ -54,10 +111,8 @@ fn navigate_inventory(
 ) {
     // TODO: Move up and down through the inventory list by publishing up and down events &
     // displaying a more detailed description of the item ONLY when selected.
-    if keys.just_released(KeyCode::ArrowUp) {
-        nav_ev.write(InvNavDir::Up);
-    } else if keys.just_released(KeyCode::ArrowDown) {
-        nav_ev.write(InvNavDir::Down);
+    if let Some(dir) = InvNavDir::from_keys(&keys) {
+        nav_ev.write(dir);
     } else if keys.just_released(KeyCode::ArrowLeft) {
         // nav_ev.write(InvNavDir::LastScreen);
         inv_index.0 = 0;
@@ -84,3 +139,34 @@ fn navigate_inventory(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{InvIndex, InvNavDir, WINDOW_ROWS};
+
+    #[test]
+    fn stepping_scrolls_the_window() {
+        let mut index = InvIndex::default();
+
+        index.step(&InvNavDir::Down, 0);
+        assert_eq!((index.0, index.1), (0, 0), "an empty list doesn't move");
+        index.step(&InvNavDir::Up, 30);
+        assert_eq!((index.0, index.1), (0, 0));
+
+        for _ in 0..WINDOW_ROWS {
+            index.step(&InvNavDir::Down, 30);
+        }
+
+        assert_eq!(index.0, WINDOW_ROWS);
+        assert!(index.1 > 0 && index.0 < index.1 + WINDOW_ROWS);
+
+        for _ in 0..100 {
+            index.step(&InvNavDir::Down, 30);
+        }
+
+        assert_eq!(index.0, 29);
+        index.clamp(5);
+        assert_eq!(index.0, 4);
+        assert!(index.1 <= index.0);
+    }
+}
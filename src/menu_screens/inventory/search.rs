@@ -0,0 +1,95 @@
+use super::{
+    InvIndex,
+    actions::{ItemMenu, handle_item_menu},
+    list::InventoryView,
+    navigate_inventory, toggle_inventory,
+};
+use crate::state::MainScreenState;
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+#[derive(Clone, Debug)]
+pub struct InventorySearchPlugin;
+
+impl Plugin for InventorySearchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InventoryView>()
+            .add_systems(
+                Update,
+                search_inventory
+                    .after(toggle_inventory)
+                    .after(navigate_inventory)
+                    .after(handle_item_menu)
+                    .run_if(in_state(MainScreenState::Inventory)),
+            )
+            .add_systems(OnExit(MainScreenState::Inventory), stop_searching);
+    }
+}
+
+pub fn not_searching(view: Res<InventoryView>) -> bool {
+    !view.editing
+}
+
+fn stop_searching(mut view: ResMut<InventoryView>) {
+    view.editing = false;
+}
+
+/// tab cycles the sort mode & "/" starts the search. while searching, typing filters the list as
+/// you go, enter keeps the search & escape clears it.
+pub fn search_inventory(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut key_evs: MessageReader<KeyboardInput>,
+    mut view: ResMut<InventoryView>,
+    mut inv_index: ResMut<InvIndex>,
+    menu: Res<ItemMenu>,
+) {
+    if !view.editing {
+        // the keys pressed before the search started aren't part of it.
+        key_evs.clear();
+
+        if menu.0.is_some() {
+            return;
+        }
+
+        if keys.just_pressed(KeyCode::Tab) {
+            view.sort = view.sort.next();
+            *inv_index = InvIndex::default();
+        } else if keys.just_pressed(KeyCode::Slash) {
+            view.editing = true;
+        }
+
+        return;
+    }
+
+    let before = view.search.clone();
+
+    for ev in key_evs.read() {
+        if ev.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &ev.logical_key {
+            Key::Enter => view.editing = false,
+            Key::Backspace => {
+                view.search.pop();
+            }
+            Key::Space => view.search.push(' '),
+            Key::Character(typed) => view.search.push_str(typed),
+            _ => {}
+        }
+    }
+
+    if keys.just_released(KeyCode::Escape) {
+        view.search.clear();
+        view.editing = false;
+    }
+
+    if view.search != before {
+        *inv_index = InvIndex::default();
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     state::MainScreenState,
     ui::{
         LookTextBody, MainTextBody, MainTextUiNode,
-        update::{update_look_section, update_main_section},
+        update::{show_command_results, update_look_section, update_main_section},
     },
 };
 use bevy::{color::palettes::tailwind::AMBER_500, prelude::*};
@@ -21,7 +21,11 @@ impl Plugin for MainUiPlugin {
         .add_systems(OnExit(MainScreenState::MainGame), default_clear_main_window)
         .add_systems(
             Update,
-            (update_main_section, update_look_section).run_if(in_state(MainScreenState::MainGame)),
+            (
+                update_main_section,
+                (update_look_section, show_command_results::<LookTextBody>).chain(),
+            )
+                .run_if(in_state(MainScreenState::MainGame)),
         );
     }
 }
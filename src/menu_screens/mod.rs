@@ -12,15 +12,22 @@ use bevy_simple_text_input::TextInputInactive;
 
 pub mod inventory;
 pub mod main_game;
+pub mod notification_history;
+pub mod player_stats;
 
 #[derive(Clone, Debug)]
 pub struct MenuScreensPlugin;
 
 impl Plugin for MenuScreensPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((inventory::InventoryPlugin, main_game::MainUiPlugin))
-            .add_systems(OnEnter(MainScreenState::MainGame), enable_cmd_prompt)
-            .add_systems(Update, change_screens.run_if(in_state(MainState::InGame)));
+        app.add_plugins((
+            inventory::InventoryPlugin,
+            main_game::MainUiPlugin,
+            notification_history::NotificationHistoryPlugin,
+            player_stats::PlayerStatsPlugin,
+        ))
+        .add_systems(OnEnter(MainScreenState::MainGame), enable_cmd_prompt)
+        .add_systems(Update, change_screens.run_if(in_state(MainState::InGame)));
     }
 }
 
@@ -0,0 +1,171 @@
+use super::{
+    default_clear_main_window, disable_cmd_prompt,
+    inventory::{InvIndex, InvNavDir, WINDOW_ROWS},
+};
+use crate::{
+    NotificationLevel, notifications::NotificationHistory, state::MainScreenState,
+    ui::MainTextUiNode,
+};
+use bevy::{color::palettes::tailwind::AMBER_500, prelude::*};
+
+#[derive(Component)]
+pub struct HistoryDisplayText;
+
+#[derive(Component)]
+pub struct HistoryTitleText;
+
+/// where the player has scrolled to in the history & which level they're looking at.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct HistoryView {
+    pub index: InvIndex,
+    /// `None` shows every level.
+    pub filter: Option<NotificationLevel>,
+}
+
+impl HistoryView {
+    /// cycles All -> Error -> Alert -> Normal, or backwards.
+    pub fn cycle_filter(&mut self, forward: bool) {
+        let filters: Vec<Option<NotificationLevel>> = std::iter::once(None)
+            .chain(NotificationLevel::ALL.into_iter().map(Some))
+            .collect();
+        let i = filters.iter().position(|f| *f == self.filter).unwrap_or(0);
+        let next = if forward {
+            (i + 1) % filters.len()
+        } else {
+            (i + filters.len() - 1) % filters.len()
+        };
+
+        self.filter = filters[next];
+        self.index = InvIndex::default();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NotificationHistoryPlugin;
+
+impl Plugin for NotificationHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HistoryView>()
+            .add_systems(
+                OnEnter(MainScreenState::NotificationHistory),
+                (disable_cmd_prompt, setup_history_menu),
+            )
+            .add_systems(
+                Update,
+                (close_history, navigate_history, display_history)
+                    .chain()
+                    .run_if(in_state(MainScreenState::NotificationHistory)),
+            )
+            .add_systems(
+                OnExit(MainScreenState::NotificationHistory),
+                default_clear_main_window,
+            );
+    }
+}
+
+fn close_history(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut screen_state: ResMut<NextState<MainScreenState>>,
+) {
+    if keys.just_released(KeyCode::Escape) {
+        screen_state.set(MainScreenState::MainGame);
+    }
+}
+
+/// up & down scroll the same way they do in the inventory, left & right change the level filter.
+fn navigate_history(
+    keys: Res<ButtonInput<KeyCode>>,
+    history: Res<NotificationHistory>,
+    mut view: ResMut<HistoryView>,
+) {
+    if let Some(dir) = InvNavDir::from_keys(&keys) {
+        let len = history.filtered(view.filter).len();
+        view.index.step(&dir, len);
+    } else if keys.just_released(KeyCode::ArrowLeft) {
+        view.cycle_filter(false);
+    } else if keys.just_released(KeyCode::ArrowRight) {
+        view.cycle_filter(true);
+    }
+}
+
+pub fn setup_history_menu(
+    mut cmds: Commands,
+    asset_server: Res<AssetServer>,
+    main_screen: Query<Entity, With<MainTextUiNode>>,
+) {
+    let text_font = TextFont {
+        font: asset_server.load("fonts/AnonymousPro.ttf"),
+        ..default()
+    };
+
+    if let Ok(main_screen) = main_screen.single() {
+        cmds.entity(main_screen).with_children(|parent| {
+            parent.spawn((
+                Text::new("Notifications"),
+                text_font.clone().with_font_size(60.0),
+                TextLayout::new(Justify::Center, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+                HistoryTitleText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                text_font.clone().with_font_size(30.0),
+                TextLayout::new(Justify::Left, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+                HistoryDisplayText,
+            ));
+        });
+    }
+}
+
+/// lists the window of notifications (newest first) with the selected one marked, colored by the
+/// level being shown.
+pub fn display_history(
+    history: Res<NotificationHistory>,
+    mut view: ResMut<HistoryView>,
+    mut title: Query<&mut Text, (With<HistoryTitleText>, Without<HistoryDisplayText>)>,
+    mut text: Query<(&mut Text, &mut TextColor), With<HistoryDisplayText>>,
+) {
+    let (Ok(mut title), Ok((mut text, mut color))) = (title.single_mut(), text.single_mut()) else {
+        return;
+    };
+
+    let notifications = history.filtered(view.filter);
+    // bypass change detection, the view only changes when the player presses a key.
+    view.bypass_change_detection()
+        .index
+        .clamp(notifications.len());
+    let index = view.index;
+
+    let new_title = match view.filter {
+        Some(level) => format!("Notifications => {}", level.label()),
+        None => "Notifications => All".into(),
+    };
+
+    let new_text = if notifications.is_empty() {
+        "No notifications".into()
+    } else {
+        notifications
+            .iter()
+            .enumerate()
+            .skip(index.1)
+            .take(WINDOW_ROWS)
+            .map(|(i, notification)| {
+                format!(
+                    "{} {: >5}s ago {} {}",
+                    if i == index.0 { ">" } else { " " },
+                    notification.time_stamp.elapsed().as_secs(),
+                    notification.level.label(),
+                    notification.mesg
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let new_color = view.filter.map_or(AMBER_500.into(), |level| level.color());
+
+    title.set_if_neq(Text(new_title));
+    text.set_if_neq(Text(new_text));
+    color.set_if_neq(TextColor(new_color));
+}
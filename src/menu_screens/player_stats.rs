@@ -0,0 +1,158 @@
+use super::{default_clear_main_window, disable_cmd_prompt};
+use crate::{
+    effects::ActiveEffects,
+    items::Stat,
+    leveling::{Experience, LevelCurve, LevelCurveAsset},
+    player::{BaseStats, EffectiveStats, Health, Player},
+    state::MainScreenState,
+    stats::{ModifierLayer, StatModifiers, apply_layer},
+    ui::MainTextUiNode,
+    wallet::Wallet,
+};
+use bevy::{color::palettes::tailwind::AMBER_500, prelude::*};
+
+#[derive(Component)]
+pub struct StatsDisplayText;
+
+#[derive(Clone, Debug)]
+pub struct PlayerStatsPlugin;
+
+impl Plugin for PlayerStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(MainScreenState::PlayerStats),
+            (disable_cmd_prompt, setup_stats_menu),
+        )
+        .add_systems(
+            Update,
+            (close_stats, display_stats).run_if(in_state(MainScreenState::PlayerStats)),
+        )
+        .add_systems(
+            OnExit(MainScreenState::PlayerStats),
+            default_clear_main_window,
+        );
+    }
+}
+
+fn close_stats(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut screen_state: ResMut<NextState<MainScreenState>>,
+) {
+    if keys.just_released(KeyCode::Escape) {
+        screen_state.set(MainScreenState::MainGame);
+    }
+}
+
+pub fn setup_stats_menu(
+    mut cmds: Commands,
+    asset_server: Res<AssetServer>,
+    main_screen: Query<Entity, With<MainTextUiNode>>,
+) {
+    let text_font = TextFont {
+        font: asset_server.load("fonts/AnonymousPro.ttf"),
+        ..default()
+    };
+
+    if let Ok(main_screen) = main_screen.single() {
+        cmds.entity(main_screen).with_children(|parent| {
+            parent.spawn((
+                Text::new("Player Stats"),
+                text_font.clone().with_font_size(60.0),
+                TextLayout::new(Justify::Center, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+            ));
+            parent.spawn((
+                Text::new(""),
+                text_font.clone().with_font_size(30.0),
+                TextLayout::new(Justify::Left, LineBreak::WordBoundary),
+                TextColor(AMBER_500.into()),
+                StatsDisplayText,
+            ));
+        });
+    }
+}
+
+/// shows every stat after each layer of modifiers, so the player can see where a number comes from.
+/// the buffs & ailments currently on the player are listed underneath.
+#[allow(clippy::type_complexity)]
+pub fn display_stats(
+    mut text: Query<&mut Text, With<StatsDisplayText>>,
+    player: Query<
+        (
+            &BaseStats,
+            &StatModifiers,
+            &EffectiveStats,
+            &Health,
+            &ActiveEffects,
+            &Wallet,
+            &Experience,
+        ),
+        With<Player>,
+    >,
+    curve: Res<LevelCurve>,
+    curves: Res<Assets<LevelCurveAsset>>,
+) {
+    let (Ok(mut text), Ok((base, modifiers, effective, health, effects, wallet, experience))) =
+        (text.single_mut(), player.single())
+    else {
+        return;
+    };
+
+    // the stats after each layer, starting with the base stats.
+    let mut layers = vec![base.0];
+
+    for layer in ModifierLayer::ALL {
+        let last = layers[layers.len() - 1];
+        layers.push(apply_layer(&last, modifiers.layer(layer)));
+    }
+
+    let next = curves
+        .get(&curve.0)
+        .and_then(|curve| curve.xp_for_next(experience.level))
+        .map(|needed| format!("{}/{needed} XP", experience.xp))
+        .unwrap_or_else(|| format!("{} XP, max level", experience.xp));
+
+    let mut lines = vec![
+        format!("Level {} ({next})", experience.level),
+        format!("HP: {:.0}/{:.0}", health.0, effective.0.hp),
+        format!("Coins: {}", wallet.coins()),
+        String::new(),
+        format!(
+            "{: <10}{: >8}{: >8}{: >8}{: >8}{: >8}{: >8}",
+            "", "Base", "Gear", "Buffs", "Status", "Load", "Total"
+        ),
+    ];
+
+    for stat in Stat::ALL {
+        let steps: String = layers
+            .iter()
+            .map(|stats| format!("{: >8.1}", stats.get(&stat)))
+            .collect();
+
+        lines.push(format!(
+            "{: <10}{steps}{: >8.1}",
+            format!("{stat:?}"),
+            effective.0.get(&stat)
+        ));
+    }
+
+    lines.push(String::new());
+
+    if effects.0.is_empty() {
+        lines.push("Effects: none".into());
+    } else {
+        lines.push("Effects:".into());
+        lines.extend(
+            effects
+                .0
+                .iter()
+                .map(|effect| format!("  {effect} ({:.0} left)", effect.remaining.max(0.0))),
+        );
+    }
+
+    let new_text = lines.join("\n");
+
+    if text.0 != new_text {
+        text.0 = new_text;
+    }
+}
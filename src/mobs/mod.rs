@@ -1,18 +1,30 @@
-use crate::HashMap;
+use crate::{HashMap, items::EquipmentEffect};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+pub type MobId = String;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Asset, TypePath)]
 pub struct MobAsset {
-    name: String,
-    spawn_rate: f32,
-    atk: f32,
-    def: f32,
-    mag_atk: f32,
-    mag_def: f32,
-    speed: f32,
-    agro: f32,
+    pub name: String,
+    pub spawn_rate: f32,
+    pub hp: f32,
+    pub atk: f32,
+    pub def: f32,
+    pub mag_atk: f32,
+    pub mag_def: f32,
+    pub speed: f32,
+    pub agro: f32,
+    /// what the mobs attacks do besides damage, ie. a chance to poison.
+    #[serde(default)]
+    pub effects: Vec<EquipmentEffect>,
+    /// the (min, max) coins the mob drops when it's beaten.
+    #[serde(default)]
+    pub coins: (u32, u32),
+    /// the XP the player gets for beating the mob.
+    #[serde(default)]
+    pub xp: u32,
 }
 
 #[derive(Debug, Clone, Default, Resource)]
-pub struct Mobs(pub HashMap<String, Handle<MobAsset>>);
+pub struct Mobs(pub HashMap<MobId, Handle<MobAsset>>);
@@ -0,0 +1,231 @@
+use crate::{Notification, NotificationLevel, state::MainState};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// how many notifications the history keeps before dropping the oldest.
+pub const HISTORY_LIMIT: usize = 200;
+
+/// every notification the player has been shown, oldest first.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct NotificationHistory(pub VecDeque<Notification>);
+
+impl NotificationHistory {
+    pub fn push(&mut self, notification: Notification) {
+        if self.0.len() >= HISTORY_LIMIT {
+            self.0.pop_front();
+        }
+
+        self.0.push_back(notification);
+    }
+
+    /// the notifications of the given level (or every level), newest first.
+    pub fn filtered(&self, level: Option<NotificationLevel>) -> Vec<&Notification> {
+        self.0
+            .iter()
+            .rev()
+            .filter(|notification| level.is_none_or(|level| notification.level == level))
+            .collect()
+    }
+}
+
+impl NotificationLevel {
+    pub const ALL: [NotificationLevel; 3] = [Self::Error, Self::Alert, Self::Normal];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Error => "[ERROR]",
+            Self::Alert => "[ALERT]",
+            Self::Normal => "[LOG]",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Error => Color::srgb_u8(255, 25, 50),
+            Self::Alert => Color::srgb_u8(255, 200, 25),
+            Self::Normal => Color::srgb_u8(10, 255, 75),
+        }
+    }
+
+    /// how long (in seconds) a toast of this level stays up. the more important, the longer.
+    pub fn toast_secs(&self) -> f32 {
+        match self {
+            Self::Error => 8.0,
+            Self::Alert => 6.0,
+            Self::Normal => 3.0,
+        }
+    }
+}
+
+/// how long a toast takes to fade out at the end of its life.
+const FADE_SECS: f32 = 1.0;
+
+/// holds the toasts, in the top right corner of the window.
+#[derive(Component)]
+pub struct ToastOverlay;
+
+#[derive(Component, Debug)]
+pub struct Toast {
+    pub level: NotificationLevel,
+    pub age: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct NotificationsPlugin;
+
+impl Plugin for NotificationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<Notification>()
+            .init_resource::<NotificationHistory>()
+            .add_systems(OnEnter(MainState::InGame), spawn_toast_overlay)
+            .add_systems(
+                Update,
+                (record_notifications, fade_toasts)
+                    .chain()
+                    .run_if(in_state(MainState::InGame)),
+            );
+    }
+}
+
+fn spawn_toast_overlay(mut cmds: Commands) {
+    cmds.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(2.5),
+            right: Val::Percent(2.5),
+            width: Val::Percent(30.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        GlobalZIndex(10),
+        ToastOverlay,
+    ));
+}
+
+/// keeps every notification in the history, and pops up a toast for it.
+fn record_notifications(
+    mut cmds: Commands,
+    mut notification_evs: MessageReader<Notification>,
+    mut history: ResMut<NotificationHistory>,
+    overlay: Query<Entity, With<ToastOverlay>>,
+    asset_server: Res<AssetServer>,
+) {
+    let text_font = TextFont {
+        font: asset_server.load("fonts/AnonymousPro.ttf"),
+        ..default()
+    };
+
+    for notification in notification_evs.read() {
+        info!("{} {}", notification.level.label(), notification.mesg);
+        history.push(notification.clone());
+
+        let Ok(overlay) = overlay.single() else {
+            continue;
+        };
+
+        let color = notification.level.color();
+
+        cmds.entity(overlay).with_children(|parent| {
+            parent.spawn((
+                Text::new(format!(
+                    "{} {}",
+                    notification.level.label(),
+                    notification.mesg
+                )),
+                text_font.clone().with_font_size(24.0),
+                TextLayout::new(Justify::Left, LineBreak::WordBoundary),
+                TextColor(color),
+                Node {
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::BLACK.with_alpha(0.85)),
+                Outline {
+                    width: Val::Px(2.0),
+                    offset: Val::Px(0.0),
+                    color,
+                },
+                Toast {
+                    level: notification.level,
+                    age: 0.0,
+                },
+            ));
+        });
+    }
+}
+
+/// how opaque a toast is at `age`. it stays solid, then fades out over its last second.
+pub fn toast_alpha(level: NotificationLevel, age: f32) -> f32 {
+    ((level.toast_secs() - age) / FADE_SECS).clamp(0.0, 1.0)
+}
+
+fn fade_toasts(
+    mut cmds: Commands,
+    time: Res<Time>,
+    mut toasts: Query<(
+        Entity,
+        &mut Toast,
+        &mut TextColor,
+        &mut BackgroundColor,
+        &mut Outline,
+    )>,
+) {
+    for (entity, mut toast, mut text_color, mut background, mut outline) in toasts.iter_mut() {
+        toast.age += time.delta_secs();
+        let alpha = toast_alpha(toast.level, toast.age);
+
+        if alpha <= 0.0 {
+            cmds.entity(entity).despawn();
+            continue;
+        }
+
+        let color = toast.level.color().with_alpha(alpha);
+        text_color.0 = color;
+        outline.color = color;
+        background.0 = Color::BLACK.with_alpha(0.85 * alpha);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HISTORY_LIMIT, NotificationHistory, toast_alpha};
+    use crate::{Notification, NotificationLevel};
+
+    #[test]
+    fn history_is_bounded_and_filterable() {
+        let mut history = NotificationHistory::default();
+
+        for i in 0..HISTORY_LIMIT + 5 {
+            let level = if i % 2 == 0 {
+                NotificationLevel::Normal
+            } else {
+                NotificationLevel::Error
+            };
+            history.push(Notification::new(level, format!("{i}")));
+        }
+
+        assert_eq!(history.0.len(), HISTORY_LIMIT);
+        assert_eq!(history.0[0].mesg, "5", "the oldest are dropped first");
+
+        let errors = history.filtered(Some(NotificationLevel::Error));
+        assert!(errors.iter().all(|n| n.level == NotificationLevel::Error));
+        assert_eq!(
+            errors[0].mesg,
+            format!("{}", HISTORY_LIMIT + 3),
+            "newest first"
+        );
+        assert_eq!(history.filtered(None).len(), HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn toasts_fade_at_the_end() {
+        assert_eq!(toast_alpha(NotificationLevel::Normal, 0.0), 1.0);
+        assert_eq!(toast_alpha(NotificationLevel::Normal, 2.5), 0.5);
+        assert_eq!(toast_alpha(NotificationLevel::Normal, 3.0), 0.0);
+        assert!(
+            toast_alpha(NotificationLevel::Error, 5.0) > 0.0,
+            "errors stay up longer"
+        );
+    }
+}
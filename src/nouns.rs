@@ -0,0 +1,404 @@
+use crate::{CommandResultEvent, TurnTaken};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// words that carry no meaning when naming a thing.
+const ARTICLES: [&str; 4] = ["the", "a", "an", "some"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    /// "take club"
+    One,
+    /// "take 3 clubs"
+    Count(usize),
+    /// "take all" or "take all clubs"
+    All,
+}
+
+/// what the player typed to refer to a thing, ie. "the 3 goblin clubs".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NounPhrase {
+    pub quantity: Quantity,
+    /// the name of the thing, lower case and without articles. empty when the player didn't name
+    /// anything (ie. "take all").
+    pub name: String,
+    /// the exact item meant, when it was picked from a menu rather than typed. skips matching by
+    /// name, so there's nothing to ask the player.
+    pub item: Option<Entity>,
+}
+
+impl NounPhrase {
+    pub fn parse(words: &[String]) -> Self {
+        let mut words: Vec<String> = words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .filter(|word| !ARTICLES.contains(&word.as_str()))
+            .collect();
+
+        let quantity = match words.first().map(|word| word.as_str()) {
+            Some("all" | "everything") => {
+                words.remove(0);
+                Quantity::All
+            }
+            Some(word) => match word.parse::<usize>() {
+                Ok(count) => {
+                    words.remove(0);
+                    Quantity::Count(count)
+                }
+                Err(_) => Quantity::One,
+            },
+            // only "all" means everything, "take the" names nothing.
+            None => Quantity::One,
+        };
+
+        Self {
+            quantity,
+            name: words.join(" "),
+            item: None,
+        }
+    }
+
+    /// one particular item, ie. the one selected on the inventory screen.
+    pub fn exact(item: Entity, name: &str) -> Self {
+        Self {
+            quantity: Quantity::One,
+            name: name.to_lowercase(),
+            item: Some(item),
+        }
+    }
+
+    /// true when the player didn't say what they meant, ie. "take 3" or "take the". "all" on its
+    /// own is fine.
+    pub fn names_nothing(&self) -> bool {
+        self.quantity != Quantity::All && self.name.is_empty()
+    }
+
+    /// how many of something the player asked for, out of how many there are.
+    pub fn how_many(&self, available: usize) -> usize {
+        match self.quantity {
+            Quantity::One => available.min(1),
+            Quantity::Count(count) => available.min(count),
+            Quantity::All => available,
+        }
+    }
+}
+
+impl std::fmt::Display for NounPhrase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.quantity {
+            Quantity::One => write!(f, "{}", self.name),
+            Quantity::Count(count) => write!(f, "{count} {}", self.name),
+            Quantity::All => write!(f, "all {}", self.name),
+        }
+    }
+}
+
+/// strips a plural ending so "clubs" can match "club".
+fn singular(word: &str) -> &str {
+    word.strip_suffix("es")
+        .filter(|stem| stem.ends_with(['s', 'x', 'h']))
+        .or_else(|| word.strip_suffix('s').filter(|stem| !stem.is_empty()))
+        .unwrap_or(word)
+}
+
+/// true if every word the player typed is the start of a word in the name. ie. "gob club" and
+/// "clubs" both match "Goblin Club".
+fn partial_match(query: &str, name: &str) -> bool {
+    let name_words: Vec<String> = name.split_whitespace().map(str::to_lowercase).collect();
+
+    !query.is_empty()
+        && query.split_whitespace().all(|word| {
+            name_words.iter().any(|name_word| {
+                name_word.starts_with(word) || name_word.starts_with(singular(word))
+            })
+        })
+}
+
+/// finds which of the names the player could mean. an exact (case insensitive) match always wins,
+/// otherwise every partial match is returned, once per distinct name.
+pub fn match_names<'a>(query: &str, names: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<&'a str> = Vec::new();
+
+    for name in names {
+        if name.eq_ignore_ascii_case(&query) {
+            return vec![name];
+        }
+
+        if partial_match(&query, name) && !matches.contains(&name) {
+            matches.push(name);
+        }
+    }
+
+    matches
+}
+
+/// what a noun phrase picked out of a group of named things.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Picked<T> {
+    /// none of the things have that name.
+    NoMatch,
+    /// the name matches more than one kind of thing, the player needs to say which.
+    Ambiguous(Vec<String>),
+    /// the things picked, at most as many as the player asked for.
+    Things(Vec<T>),
+}
+
+/// picks the things the player meant out of `(thing, name)` pairs. a phrase without a name picks
+/// from everything.
+pub fn pick<T: Clone>(phrase: &NounPhrase, things: &[(T, String)]) -> Picked<T> {
+    if phrase.name.is_empty() {
+        let count = phrase.how_many(things.len());
+        return Picked::Things(
+            things[..count]
+                .iter()
+                .map(|(thing, _)| thing.clone())
+                .collect(),
+        );
+    }
+
+    let matches = match_names(&phrase.name, things.iter().map(|(_, name)| name.as_str()));
+
+    let wanted = match matches.as_slice() {
+        [] => return Picked::NoMatch,
+        [name] => *name,
+        _ => return Picked::Ambiguous(matches.iter().map(|name| name.to_string()).collect()),
+    };
+
+    let named: Vec<T> = things
+        .iter()
+        .filter(|(_, name)| name == wanted)
+        .map(|(thing, _)| thing.clone())
+        .collect();
+    let count = phrase.how_many(named.len());
+
+    Picked::Things(named[..count].to_vec())
+}
+
+/// `pick`, except a phrase naming an exact item only ever picks that item. `entity` says which item
+/// a thing is.
+pub fn pick_item<T: Clone>(
+    phrase: &NounPhrase,
+    things: &[(T, String)],
+    entity: impl Fn(&T) -> Option<Entity>,
+) -> Picked<T> {
+    let Some(item) = phrase.item else {
+        return pick(phrase, things);
+    };
+
+    match things.iter().find(|(thing, _)| entity(thing) == Some(item)) {
+        Some((thing, _)) => Picked::Things(vec![thing.clone()]),
+        None => Picked::NoMatch,
+    }
+}
+
+/// a question the game asked because the player could have meant more than one thing. the next
+/// thing they type is checked against the options before being parsed as a command.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PendingQuestion(pub Option<Disambiguation>);
+
+#[derive(Debug, Clone)]
+pub struct Disambiguation {
+    /// the start of the command to re-run with the chosen option, ie. "take 3".
+    pub command: String,
+    pub options: Vec<String>,
+}
+
+impl Disambiguation {
+    pub fn new(verb: &str, phrase: &NounPhrase, options: Vec<String>) -> Self {
+        let command = match phrase.quantity {
+            Quantity::One => verb.to_string(),
+            Quantity::Count(count) => format!("{verb} {count}"),
+            Quantity::All => format!("{verb} all"),
+        };
+
+        Self { command, options }
+    }
+
+    pub fn question(&self) -> String {
+        let options: Vec<String> = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| format!("{}) {option}", i + 1))
+            .collect();
+
+        format!("Which do you mean? {}", options.join(", "))
+    }
+
+    /// turns the players answer in to the full command. the answer can either be the number of an
+    /// option, or (part of) its name.
+    pub fn answer(&self, answer: &str) -> Option<String> {
+        let answer = answer.trim();
+        let option = match answer.parse::<usize>() {
+            Ok(i) => self.options.get(i.checked_sub(1)?)?.clone(),
+            Err(_) => {
+                let matches = match_names(answer, self.options.iter().map(String::as_str));
+                let [option] = matches.as_slice() else {
+                    return None;
+                };
+
+                option.to_string()
+            }
+        };
+
+        Some(format!("{} {option}", self.command))
+    }
+}
+
+/// how a command that names things answers the player.
+#[derive(SystemParam)]
+pub struct CommandReply<'w> {
+    result_ev: MessageWriter<'w, CommandResultEvent>,
+    question: ResMut<'w, PendingQuestion>,
+    turn_ev: MessageWriter<'w, TurnTaken>,
+}
+
+impl CommandReply<'_> {
+    pub fn write(&mut self, result: CommandResultEvent) {
+        self.result_ev.write(result);
+    }
+
+    pub fn success(&mut self, text: impl Into<String>) {
+        self.write(CommandResultEvent::success(text));
+    }
+
+    pub fn failure(&mut self, text: impl Into<String>) {
+        self.write(CommandResultEvent::failure(text));
+    }
+
+    /// asks which thing the player meant, their answer re-runs the command.
+    pub fn ask(&mut self, ask: Disambiguation) {
+        self.success(ask.question());
+        self.question.0 = Some(ask);
+    }
+
+    /// the command changed something, so it used up the player's turn.
+    pub fn took_turn(&mut self) {
+        self.turn_ev.write_default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Disambiguation, NounPhrase, Picked, Quantity, match_names, pick, pick_item};
+    use bevy::prelude::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn noun_phrases() {
+        assert_eq!(
+            NounPhrase::parse(&words("the Goblin Club")),
+            NounPhrase {
+                quantity: Quantity::One,
+                name: "goblin club".into(),
+                item: None,
+            }
+        );
+        assert_eq!(
+            NounPhrase::parse(&words("3 clubs")),
+            NounPhrase {
+                quantity: Quantity::Count(3),
+                name: "clubs".into(),
+                item: None,
+            }
+        );
+        assert_eq!(NounPhrase::parse(&words("all")).quantity, Quantity::All);
+        assert_eq!(NounPhrase::parse(&words("the")).quantity, Quantity::One);
+        assert!(NounPhrase::parse(&words("3")).names_nothing());
+        assert!(NounPhrase::parse(&words("a")).names_nothing());
+        assert!(NounPhrase::parse(&[]).names_nothing());
+        assert!(!NounPhrase::parse(&words("all")).names_nothing());
+    }
+
+    #[test]
+    fn name_matching() {
+        let names = [
+            "Goblin Club",
+            "Goblin Club",
+            "Health Potion",
+            "Club of Doom",
+        ];
+
+        assert_eq!(match_names("goblin club", names), ["Goblin Club"]);
+        assert_eq!(match_names("gob", names), ["Goblin Club"]);
+        assert_eq!(match_names("potions", names), ["Health Potion"]);
+        assert_eq!(match_names("clubs", names), ["Goblin Club", "Club of Doom"]);
+        assert!(match_names("nut cracker 9000", names).is_empty());
+    }
+
+    #[test]
+    fn picking_things() {
+        let things = [
+            (0, "Goblin Club".to_string()),
+            (1, "Health Potion".to_string()),
+            (2, "Goblin Club".to_string()),
+            (3, "Club of Doom".to_string()),
+        ];
+
+        assert_eq!(
+            pick(&NounPhrase::parse(&words("goblin club")), &things),
+            Picked::Things(vec![0])
+        );
+        assert_eq!(
+            pick(&NounPhrase::parse(&words("5 goblin clubs")), &things),
+            Picked::Things(vec![0, 2])
+        );
+        assert_eq!(
+            pick(&NounPhrase::parse(&words("all")), &things),
+            Picked::Things(vec![0, 1, 2, 3])
+        );
+        assert_eq!(
+            pick(&NounPhrase::parse(&words("2")), &things),
+            Picked::Things(vec![0, 1])
+        );
+        assert_eq!(
+            pick(&NounPhrase::parse(&words("clubs")), &things),
+            Picked::Ambiguous(vec!["Goblin Club".into(), "Club of Doom".into()])
+        );
+        assert_eq!(
+            pick(&NounPhrase::parse(&words("sword")), &things),
+            Picked::NoMatch
+        );
+    }
+
+    #[test]
+    fn picking_an_exact_item() {
+        let club = Entity::from_raw_u32(1).unwrap();
+        let other_club = Entity::from_raw_u32(2).unwrap();
+        let things = [
+            (club, "Goblin Club".to_string()),
+            (other_club, "Goblin Club".to_string()),
+        ];
+
+        let pick_exact = |item| {
+            pick_item(&NounPhrase::exact(item, "Goblin Club"), &things, |thing| {
+                Some(*thing)
+            })
+        };
+
+        assert_eq!(pick_exact(other_club), Picked::Things(vec![other_club]));
+        assert_eq!(
+            pick_exact(Entity::from_raw_u32(3).unwrap()),
+            Picked::NoMatch
+        );
+    }
+
+    #[test]
+    fn answering_a_question() {
+        let question = Disambiguation {
+            command: "take 3".into(),
+            options: vec!["Goblin Club".into(), "Club of Doom".into()],
+        };
+
+        assert_eq!(question.answer("2").as_deref(), Some("take 3 Club of Doom"));
+        assert_eq!(
+            question.answer("goblin").as_deref(),
+            Some("take 3 Goblin Club")
+        );
+        assert_eq!(question.answer("club"), None);
+        assert_eq!(question.answer("0"), None);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{
+    ailments::roll_proc,
+    effects::{ActiveEffect, ActiveEffects, EffectKind},
+    equipment::Equipment,
+    items::{Amount, ConsumableEffect, ItemAsset, ItemType, Stat},
+    leveling::Experience,
+    mobs::MobAsset,
+    stats::StatModifiers,
+    wallet::Wallet,
+};
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// where the player starts a new game, and wakes up after being defeated.
+pub const SPAWN_ZONE: &str = "starter-town/gate.zone.ron";
+
+/// marks the entity that represents the player.
+#[derive(Component, Debug, Default)]
+pub struct Player;
+
+/// the numbers that decide how a fight goes. used by both the player & mobs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CombatStats {
+    pub hp: f32,
+    pub mana: f32,
+    pub attack: f32,
+    pub defence: f32,
+    pub magic_atk: f32,
+    pub magic_def: f32,
+    pub speed: f32,
+}
+
+impl CombatStats {
+    pub fn get(&self, stat: &Stat) -> f32 {
+        match stat {
+            Stat::HP => self.hp,
+            Stat::Mana => self.mana,
+            Stat::Attack => self.attack,
+            Stat::Defence => self.defence,
+            Stat::MagicAtk => self.magic_atk,
+            Stat::MagicDef => self.magic_def,
+            Stat::Speed => self.speed,
+        }
+    }
+
+    pub fn get_mut(&mut self, stat: &Stat) -> &mut f32 {
+        match stat {
+            Stat::HP => &mut self.hp,
+            Stat::Mana => &mut self.mana,
+            Stat::Attack => &mut self.attack,
+            Stat::Defence => &mut self.defence,
+            Stat::MagicAtk => &mut self.magic_atk,
+            Stat::MagicDef => &mut self.magic_def,
+            Stat::Speed => &mut self.speed,
+        }
+    }
+}
+
+impl From<&MobAsset> for CombatStats {
+    fn from(mob: &MobAsset) -> Self {
+        Self {
+            hp: mob.hp,
+            mana: 0.0,
+            attack: mob.atk,
+            defence: mob.def,
+            magic_atk: mob.mag_atk,
+            magic_def: mob.mag_def,
+            speed: mob.speed,
+        }
+    }
+}
+
+/// the players stats before any items or effects are taken into account.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct BaseStats(pub CombatStats);
+
+impl Default for BaseStats {
+    fn default() -> Self {
+        Self(CombatStats {
+            hp: 30.0,
+            mana: 10.0,
+            attack: 6.0,
+            defence: 2.0,
+            magic_atk: 1.0,
+            magic_def: 1.0,
+            speed: 3.0,
+        })
+    }
+}
+
+/// the players stats with every modifier taken into account, see `stats::effective_stats`. this is
+/// what fights use.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct EffectiveStats(pub CombatStats);
+
+/// how much HP the player has left. the max comes from the players stats.
+#[derive(Component, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Health(pub f32);
+
+impl Health {
+    pub fn heal(&mut self, amount: &Amount, max: f32) -> f32 {
+        let before = self.0;
+        let amount = match amount {
+            Amount::Fixed(hp) => *hp as f32,
+            Amount::Percent(percent) => max * percent,
+        };
+        self.0 = (self.0 + amount).clamp(0.0, max);
+
+        self.0 - before
+    }
+}
+
+pub fn spawn_player(mut cmds: Commands, player: Query<Entity, With<Player>>) {
+    if !player.is_empty() {
+        return;
+    }
+
+    let stats = BaseStats::default();
+    let effective = EffectiveStats(stats.0);
+    let health = Health(stats.0.hp);
+    cmds.spawn((
+        Player,
+        stats,
+        StatModifiers::default(),
+        ActiveEffects::default(),
+        effective,
+        health,
+        Equipment::default(),
+        Wallet::default(),
+        Experience::default(),
+    ));
+}
+
+/// applies the effects of a consumable (or key item) to the player, returning a description of what
+/// happened. effects with a duration are started on `active` and play out over time. `rng` decides
+/// whether ailments take hold.
+pub fn apply_consumable<R: Rng>(
+    item: &ItemAsset,
+    health: &mut Health,
+    stats: &CombatStats,
+    active: &mut ActiveEffects,
+    rng: &mut R,
+) -> Vec<String> {
+    let (ItemType::Consumable { effects } | ItemType::KeyItem { effects }) = &item.item_data else {
+        return vec![format!("The {} can't be used like that.", item.name)];
+    };
+
+    effects
+        .iter()
+        .map(|effect| match effect {
+            ConsumableEffect::Heal { amount, duration } if *duration > 0.0 => {
+                let kind = EffectKind::HealOverTime {
+                    amount: amount.clone(),
+                };
+                active.add(ActiveEffect::new(&item.name, kind, *duration));
+                format!("The {} starts to heal you.", item.name)
+            }
+            ConsumableEffect::Heal { amount, .. } => {
+                let healed = health.heal(amount, stats.hp);
+                format!("The {} restores {healed:.0} HP.", item.name)
+            }
+            ConsumableEffect::Buf {
+                stat,
+                amount,
+                duration,
+            } if *duration > 0.0 => {
+                let kind = EffectKind::Buf {
+                    stat: *stat,
+                    amount: amount.clone(),
+                };
+                active.add(ActiveEffect::new(&item.name, kind, *duration));
+                format!("The {} bolsters your {stat:?}.", item.name)
+            }
+            ConsumableEffect::Buf { stat, .. } => {
+                format!(
+                    "The {} should buf your {stat:?}, but it wears off right away.",
+                    item.name
+                )
+            }
+            ConsumableEffect::InflictStatus {
+                ailment,
+                duration,
+                chance,
+            } if *duration > 0.0 && roll_proc(rng, *chance) => {
+                let kind = EffectKind::Ailment { ailment: *ailment };
+                active.add(ActiveEffect::new(&item.name, kind, *duration));
+                format!("The {} leaves you feeling off.", item.name)
+            }
+            ConsumableEffect::InflictStatus { .. } => format!("The {} fizzles.", item.name),
+            ConsumableEffect::Cure { ailments } => {
+                let cured: Vec<String> = active
+                    .cure(ailments)
+                    .iter()
+                    .map(|effect| effect.to_string())
+                    .collect();
+
+                if cured.is_empty() {
+                    format!("The {} has nothing to cure.", item.name)
+                } else {
+                    format!("The {} cures your {}.", item.name, cured.join(", "))
+                }
+            }
+            // the gear is fixed up by whoever has the equipment, see `equipment::repair_equipped`.
+            ConsumableEffect::Repair { .. } => {
+                format!("You work on your gear with the {}.", item.name)
+            }
+        })
+        .collect()
+}
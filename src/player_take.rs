@@ -1,43 +1,192 @@
 use crate::{
-    PlayerTake,
-    items::InventoryEntry,
-    zones::{Location, ZoneAsset, Zones},
+    CommandResultEvent, GenerincFlavorText, PlayerTake,
+    carry::CarryLoad,
+    items::{InventoryItems, ItemId, add_to_inventory},
+    nouns::{CommandReply, Disambiguation, Picked, Quantity, pick},
+    world::{GroundItem, WorldState},
+    zones::{CurrentZone, FlavorTextType},
 };
 use bevy::prelude::*;
 
-/// displays more information to the player.
+/// moves items from the players zone into their inventory.
 pub fn handle_player_take(
     mut cmds: Commands,
     mut player_take_evs: MessageReader<PlayerTake>,
-    zone_assets: Res<Assets<ZoneAsset>>,
-    zones: Res<Zones>,
-    location: Res<Location>,
-    mut asset_server: ResMut<AssetServer>,
+    mut reply: CommandReply,
+    inventory: InventoryItems,
+    load: CarryLoad,
+    here: CurrentZone,
+    mut world: ResMut<WorldState>,
 ) {
-    let loc = location.0.clone();
+    let loc = &here.location.0;
+    let items = &inventory.items;
 
-    for _ev in player_take_evs.read() {
-        if let Some(at) = zones.0.get(&loc) {
-            // get where they are and the "examine" text in one step.
-            let Some(items) = zone_assets.get(at).map(|asset| asset.items.clone()) else {
+    for PlayerTake(thing) in player_take_evs.read() {
+        let Some(zone_asset) = here.get() else {
+            error!(
+                "the player is at a location that is unknown to the engine. something went VERY wrong."
+            );
+            error!("location: {loc}");
+            continue;
+        };
+        let zone = world.zone_mut(loc, zone_asset);
+
+        // (index into the zones items, item name)
+        let here: Vec<(usize, String)> = zone
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, on_ground)| {
+                let asset = items.get(&on_ground.item)?;
+                Some((i, asset.name.clone()))
+            })
+            .collect();
+
+        if here.is_empty() {
+            reply.write(CommandResultEvent(Err(GenerincFlavorText::Random(
+                FlavorTextType::NothingToTake,
+            ))));
+            continue;
+        }
+
+        let indices = match pick(thing, &here) {
+            Picked::NoMatch => {
+                reply.failure(format!("There is no {} here.", thing.name));
+                continue;
+            }
+            Picked::Ambiguous(options) => {
+                reply.ask(Disambiguation::new("take", thing, options));
+                continue;
+            }
+            Picked::Things(indices) if indices.is_empty() => {
+                reply.failure("You take nothing.");
+                continue;
+            }
+            Picked::Things(indices) => indices,
+        };
+        let count = indices.len();
+
+        // take as many as the player can carry, in order.
+        let capacity = load.capacity();
+        let mut carried = load.weight();
+        let mut too_heavy = None;
+        let mut fits = Vec::new();
+
+        for i in indices {
+            let Some(asset) = items.get(&zone.items[i].item) else {
                 continue;
             };
 
-            if items.len() > 0 {
-                for item in items {
-                    info!("got: {item}");
-                    cmds.spawn(InventoryEntry { asset_path: item });
-                }
-            } else {
-                // TODO: issue "no items to take, command failed" notification.
+            if carried + asset.weight > capacity + f32::EPSILON {
+                too_heavy = Some(asset.name.clone());
+                break;
             }
 
-            info!("player acquired all items from zone: {loc:?}");
-        } else {
-            error!(
-                "the player is at a location that is unknown to the engine. something went VERY wrong."
-            );
-            error!("{zones:?}.get({loc})")
+            carried += asset.weight;
+            fits.push(i);
+        }
+
+        if fits.is_empty() {
+            if let Some(too_heavy) = &too_heavy {
+                reply.failure(format!(
+                    "The {too_heavy} is too heavy, you're already carrying {carried:.1} of \
+                     {capacity:.1}."
+                ));
+            }
+
+            continue;
+        }
+
+        let indices = fits;
+
+        // remove from the back so the indices stay valid.
+        let mut taken: Vec<_> = indices
+            .iter()
+            .rev()
+            .map(|i| zone.items.remove(*i))
+            .collect();
+        taken.reverse();
+
+        let described = items.describe(taken.iter().map(|on_ground| &on_ground.item));
+        let mut message = format!("You take: {}.", described.unwrap_or_default());
+
+        if let Quantity::Count(asked_for) = thing.quantity
+            && asked_for > count
+        {
+            message = format!("There were only {count}. {message}");
+        }
+
+        if too_heavy.is_some() {
+            message = format!("{message} You can't carry any more. ({carried:.1}/{capacity:.1})");
         }
+
+        for on_ground in taken.iter() {
+            info!("got: {}", on_ground.item);
+        }
+
+        for (item_id, durability) in group_by_item(taken) {
+            if let Some(asset) = items.get(&item_id) {
+                add_to_inventory(
+                    &mut cmds,
+                    inventory.entries.iter(),
+                    &item_id,
+                    asset,
+                    durability.len(),
+                    &durability,
+                );
+            }
+        }
+
+        reply.success(message);
+        reply.took_turn();
+    }
+}
+
+/// the durability left on each of the taken items, grouped by item. worn & new ones of the same item
+/// go in together so the stacks are only planned once.
+fn group_by_item(taken: Vec<GroundItem>) -> Vec<(ItemId, Vec<Option<u32>>)> {
+    let mut grouped: Vec<(ItemId, Vec<Option<u32>>)> = Vec::new();
+
+    for on_ground in taken {
+        match grouped.iter_mut().find(|(item, _)| *item == on_ground.item) {
+            Some((_, durability)) => durability.push(on_ground.durability),
+            None => grouped.push((on_ground.item, vec![on_ground.durability])),
+        }
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod test {
+    use super::group_by_item;
+    use crate::{items::plan_stacks, world::GroundItem};
+
+    #[test]
+    fn worn_and_new_items_are_taken_together() {
+        let ground = |item: &str, durability| GroundItem {
+            item: item.into(),
+            durability,
+        };
+        let taken = vec![
+            ground("potion", None),
+            ground("club", Some(3)),
+            ground("potion", None),
+            ground("club", None),
+        ];
+        let grouped = group_by_item(taken);
+
+        assert_eq!(
+            grouped,
+            [
+                ("potion".to_string(), vec![None, None]),
+                ("club".to_string(), vec![Some(3), None])
+            ]
+        );
+        // one plan for both potions, so the stack isn't topped up twice.
+        assert_eq!(
+            plan_stacks(&[9], 10, grouped[0].1.len()),
+            (vec![(0, 10)], vec![1])
+        );
     }
 }
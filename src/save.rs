@@ -0,0 +1,487 @@
+use crate::{
+    Autosave, CommandResultEvent, LoadGame, NewZone, Notification, NotificationLevel, SaveGame,
+    effects::ActiveEffects,
+    equipment::{Equipment, SavedEquipment},
+    items::InventoryEntry,
+    leveling::Experience,
+    player::{Health, Player},
+    state::GameState,
+    ui::update::UpdateLookSectionText,
+    wallet::Wallet,
+    world::WorldState,
+    zones::Location,
+};
+use anyhow::{Context, bail};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// the version of the save file layout written by this build. bump this when a change to
+/// `SaveState` can't be papered over with `#[serde(default)]`.
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+/// where save files are written to (relative to the working dir, like "assets/").
+pub const SAVE_DIR: &str = "saves";
+
+/// identifies a save file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SaveSlot {
+    /// a save slot the player wrote to with "/save".
+    Manual(u8),
+    /// an autosave generation. 0 is the newest, older generations have higher numbers.
+    Auto(usize),
+}
+
+impl SaveSlot {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Self::Manual(slot) => PathBuf::from(SAVE_DIR).join(format!("slot-{slot}.save.ron")),
+            Self::Auto(generation) => {
+                PathBuf::from(SAVE_DIR).join(format!("autosave-{generation}.save.ron"))
+            }
+        }
+    }
+}
+
+impl Display for SaveSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Manual(slot) => write!(f, "slot {slot}"),
+            Self::Auto(_) => write!(f, "the autosave"),
+        }
+    }
+}
+
+/// controls when the game autosaves on its own.
+#[derive(Debug, Clone, Resource)]
+pub struct AutosaveConfig {
+    /// also autosave every time the player walks into a new zone.
+    pub on_zone_change: bool,
+    /// how many autosaves to keep around before the oldest is deleted.
+    pub generations: usize,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            on_zone_change: false,
+            generations: 3,
+        }
+    }
+}
+
+/// a snapshot of everything about the player that should survive a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveState {
+    /// the zone the player was in.
+    pub location: Location,
+    /// every item in the players inventory.
+    pub inventory: Vec<InventoryEntry>,
+    /// which of the inventory items are equipped.
+    pub equipment: SavedEquipment,
+    /// the timed effects that were still running on the player.
+    pub effects: ActiveEffects,
+    /// how much HP the player had left. (None in saves from before the player could be hurt)
+    pub health: Option<Health>,
+    /// the coins the player had. (None in saves from before the player had money)
+    pub wallet: Option<Wallet>,
+    /// the players XP & level. (None in saves from before the player could level up)
+    pub experience: Option<Experience>,
+    /// the changes the player made to the world, ie. items they took or dropped.
+    pub world: WorldState,
+}
+
+/// what actually gets written to disk. the version comes first so it can be checked before the
+/// rest of the file is parsed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub state: SaveState,
+}
+
+/// only used to peek at the version of a save file.
+#[derive(Debug, Clone, Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+/// writes the save to a temporary file next to `path`. the caller moves it into place once it is
+/// fully on disk, that way a crash mid-write can never clobber an existing save.
+fn write_temp_save(path: &Path, state: &SaveState) -> anyhow::Result<PathBuf> {
+    let file = SaveFile {
+        version: SAVE_FORMAT_VERSION,
+        state: state.clone(),
+    };
+    let text = ron::ser::to_string_pretty(&file, PrettyConfig::default())?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create save directory {dir:?}"))?;
+    }
+
+    // synced to disk before it's renamed over the real save, so a crash can't leave a half
+    // written save behind.
+    let tmp_path = path.with_extension("ron.tmp");
+    let mut tmp = File::create(&tmp_path)
+        .with_context(|| format!("failed to create save file {tmp_path:?}"))?;
+    tmp.write_all(text.as_bytes())
+        .and_then(|_| tmp.sync_all())
+        .with_context(|| format!("failed to write save file {tmp_path:?}"))?;
+
+    Ok(tmp_path)
+}
+
+pub fn write_save(path: &Path, state: &SaveState) -> anyhow::Result<()> {
+    let tmp_path = write_temp_save(path, state)?;
+
+    fs::rename(&tmp_path, path).with_context(|| format!("failed to move save into {path:?}"))
+}
+
+/// writes a new autosave, shifting the existing ones back a generation and dropping the oldest.
+pub fn write_autosave(state: &SaveState, generations: usize) -> anyhow::Result<()> {
+    let newest = SaveSlot::Auto(0).path();
+    // write first so a failed write leaves the old generations untouched.
+    let tmp_path = write_temp_save(&newest, state)?;
+
+    for generation in (0..generations.saturating_sub(1)).rev() {
+        let from = SaveSlot::Auto(generation).path();
+
+        if from.exists() {
+            let to = SaveSlot::Auto(generation + 1).path();
+            fs::rename(&from, &to)
+                .with_context(|| format!("failed to rotate autosave {from:?} to {to:?}"))?;
+        }
+    }
+
+    fs::rename(&tmp_path, &newest)
+        .with_context(|| format!("failed to move autosave into {newest:?}"))
+}
+
+/// finds the most recently written autosave, if there is one.
+pub fn newest_autosave(generations: usize) -> Option<(SaveSlot, SystemTime)> {
+    (0..generations)
+        .map(SaveSlot::Auto)
+        .filter_map(|slot| {
+            let modified = fs::metadata(slot.path()).ok()?.modified().ok()?;
+            Some((slot, modified))
+        })
+        .max_by_key(|(_slot, modified)| *modified)
+}
+
+pub fn read_save(path: &Path) -> anyhow::Result<SaveState> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read save file {path:?}"))?;
+    let header: SaveHeader = ron::from_str(&text)
+        .with_context(|| format!("{path:?} is not a save file (missing version header)"))?;
+
+    if header.version > SAVE_FORMAT_VERSION {
+        bail!(
+            "{path:?} was saved by a newer version of the game (save format v{}, this build reads up to v{SAVE_FORMAT_VERSION})",
+            header.version
+        );
+    }
+
+    let file: SaveFile =
+        ron::from_str(&text).with_context(|| format!("save file {path:?} is corrupt"))?;
+
+    Ok(file.state)
+}
+
+/// everything that goes into a save file.
+#[derive(SystemParam)]
+pub struct SaveData<'w, 's> {
+    location: Res<'w, Location>,
+    inventory: Query<'w, 's, (Entity, &'static InventoryEntry)>,
+    player: Query<
+        'w,
+        's,
+        (
+            &'static Health,
+            &'static Equipment,
+            &'static ActiveEffects,
+            &'static Wallet,
+            &'static Experience,
+        ),
+        With<Player>,
+    >,
+    world: Res<'w, WorldState>,
+}
+
+impl SaveData<'_, '_> {
+    /// collects the current game state into a `SaveState`.
+    pub fn snapshot(&self) -> SaveState {
+        let (entities, inventory): (Vec<Entity>, Vec<InventoryEntry>) = self
+            .inventory
+            .iter()
+            .map(|(entity, entry)| (entity, entry.clone()))
+            .unzip();
+        let player = self.player.single().ok();
+
+        SaveState {
+            location: self.location.clone(),
+            inventory,
+            equipment: player
+                .map(|(_, equipment, _, _, _)| SavedEquipment::new(equipment, &entities))
+                .unwrap_or_default(),
+            effects: player
+                .map(|(_, _, effects, _, _)| effects.clone())
+                .unwrap_or_default(),
+            health: player.map(|(health, _, _, _, _)| *health),
+            wallet: player.map(|(_, _, _, wallet, _)| *wallet),
+            experience: player.map(|(_, _, _, _, experience)| *experience),
+            world: self.world.clone(),
+        }
+    }
+}
+
+/// writes the game state to the requested save slot.
+pub fn handle_save_game(
+    mut save_evs: MessageReader<SaveGame>,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+    save_data: SaveData,
+) {
+    for ev in save_evs.read() {
+        let path = ev.0.path();
+
+        match write_save(&path, &save_data.snapshot()) {
+            Ok(_) => {
+                info!("saved game to {path:?}");
+                result_ev.write(CommandResultEvent::success(format!(
+                    "Game saved to {}.",
+                    ev.0
+                )));
+            }
+            Err(e) => {
+                error!("saving to {} failed: {e:?}", ev.0);
+                result_ev.write(CommandResultEvent::failure(format!(
+                    "Could not save to {}: {e}",
+                    ev.0
+                )));
+            }
+        }
+    }
+}
+
+/// writes an autosave whenever one is requested.
+pub fn handle_autosave(
+    mut autosave_evs: MessageReader<Autosave>,
+    mut notification_ev: MessageWriter<Notification>,
+    config: Res<AutosaveConfig>,
+    save_data: SaveData,
+) {
+    // several requests in one frame would all save the same state, so only write once.
+    if autosave_evs.read().count() == 0 {
+        return;
+    }
+
+    if let Err(e) = write_autosave(&save_data.snapshot(), config.generations) {
+        error!("autosave failed: {e:?}");
+        notification_ev.write(Notification::new(
+            NotificationLevel::Error,
+            format!("Autosave failed: {e}"),
+        ));
+    } else {
+        debug!("autosaved");
+    }
+}
+
+/// autosaves before the game is torn down.
+pub fn autosave_on_wrapup(config: Res<AutosaveConfig>, save_data: SaveData) {
+    match write_autosave(&save_data.snapshot(), config.generations) {
+        Ok(_) => info!("autosaved before exiting"),
+        Err(e) => error!("autosave on exit failed: {e:?}"),
+    }
+}
+
+/// lets the player know they can pick up where they left off.
+pub fn offer_resume(
+    config: Res<AutosaveConfig>,
+    mut look_event: MessageWriter<UpdateLookSectionText>,
+) {
+    let Some((_slot, modified)) = newest_autosave(config.generations) else {
+        return;
+    };
+
+    let age = modified
+        .elapsed()
+        .map(|age| format!(" from {} minute(s) ago", age.as_secs() / 60))
+        .unwrap_or_default();
+
+    look_event.write(UpdateLookSectionText(format!(
+        "Found an autosave{age}. Enter \"/resume\" to continue where you left off."
+    )));
+}
+
+/// replaces the current game state with the one stored in the requested save slot. not allowed
+/// mid fight or in a shop, the battle or shop visit would carry on in the loaded world.
+pub fn handle_load_game(
+    mut load_evs: MessageReader<LoadGame>,
+    mut new_zone_ev: MessageWriter<NewZone>,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+    mut target: LoadTarget,
+    mut look_event: MessageWriter<UpdateLookSectionText>,
+    game_state: Res<State<GameState>>,
+) {
+    for ev in load_evs.read() {
+        match game_state.get() {
+            GameState::Battle => {
+                result_ev.write(CommandResultEvent::failure(
+                    "You can't load a game in the middle of a fight.",
+                ));
+                continue;
+            }
+            GameState::Shopping => {
+                result_ev.write(CommandResultEvent::failure(
+                    "Leave the shop before loading a game.",
+                ));
+                continue;
+            }
+            _ => {}
+        }
+
+        let path = ev.0.path();
+
+        let state = match read_save(&path) {
+            Ok(state) => state,
+            Err(e) => {
+                error!("loading {} failed: {e:?}", ev.0);
+                result_ev.write(CommandResultEvent::failure(format!(
+                    "Could not load {}: {e}",
+                    ev.0
+                )));
+                continue;
+            }
+        };
+
+        target.restore(state);
+        look_event.write(UpdateLookSectionText(String::new()));
+        new_zone_ev.write_default();
+        info!("loaded game from {path:?}");
+    }
+}
+
+/// everything a save file is loaded in to.
+#[derive(SystemParam)]
+pub struct LoadTarget<'w, 's> {
+    cmds: Commands<'w, 's>,
+    location: ResMut<'w, Location>,
+    world: ResMut<'w, WorldState>,
+    inventory: Query<'w, 's, Entity, With<InventoryEntry>>,
+    player: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static mut Health,
+            &'static mut Wallet,
+            &'static mut Experience,
+        ),
+        With<Player>,
+    >,
+}
+
+impl LoadTarget<'_, '_> {
+    /// replaces the current game state with `state`.
+    pub fn restore(&mut self, state: SaveState) {
+        let player = self.player.single_mut().ok().map(
+            |(player, mut health, mut wallet, mut experience)| {
+                if let Some(saved) = state.health {
+                    *health = saved;
+                }
+
+                // restoring a save isn't a gain or a loss, so this skips `Purse`.
+                *wallet = state.wallet.unwrap_or_default();
+                *experience = state.experience.unwrap_or_default();
+
+                player
+            },
+        );
+
+        *self.location = state.location;
+        *self.world = state.world;
+
+        for entry in self.inventory.iter() {
+            self.cmds.entity(entry).despawn();
+        }
+
+        let entries: Vec<Entity> = state
+            .inventory
+            .into_iter()
+            .map(|entry| self.cmds.spawn(entry).id())
+            .collect();
+
+        if let Some(player) = player {
+            self.cmds
+                .entity(player)
+                .insert((state.equipment.restore(&entries), state.effects));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SAVE_FORMAT_VERSION, SaveState, read_save, write_save};
+    use crate::{equipment::SavedEquipment, items::InventoryEntry, zones::Location};
+    use std::{fs, path::PathBuf, process};
+
+    /// a save path no other test (or test run) is using at the same time.
+    fn temp_path(test: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("xork-test-{test}-{}.save.ron", process::id()))
+    }
+
+    #[test]
+    fn save_round_trip() {
+        let path = temp_path("round-trip");
+        let state = SaveState {
+            location: Location("starter-woods/02.zone.ron".into()),
+            inventory: vec![InventoryEntry::new("items/goblin-club.item.ron", 3)],
+            equipment: SavedEquipment {
+                main_hand: Some(0),
+                ..Default::default()
+            },
+            effects: Default::default(),
+            health: None,
+            wallet: None,
+            experience: None,
+            world: Default::default(),
+        };
+
+        write_save(&path, &state).unwrap();
+        let loaded = read_save(&path).unwrap();
+        _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.location.0, state.location.0);
+        assert_eq!(loaded.inventory.len(), 1);
+        assert_eq!(
+            loaded.inventory[0].asset_path,
+            state.inventory[0].asset_path
+        );
+        assert_eq!(loaded.inventory[0].count, 3);
+        assert_eq!(loaded.equipment, state.equipment);
+    }
+
+    #[test]
+    fn newer_save_version_is_rejected() {
+        let path = temp_path("future");
+        fs::write(
+            &path,
+            format!(
+                "(version: {}, state: (location: (\"x\"), inventory: []))",
+                SAVE_FORMAT_VERSION + 1
+            ),
+        )
+        .unwrap();
+
+        let loaded = read_save(&path);
+        _ = fs::remove_file(&path);
+
+        assert!(loaded.is_err(), "a save from the future should not load");
+    }
+}
@@ -0,0 +1,554 @@
+use crate::{
+    CommandResultEvent, HashMap, NewZone,
+    carry::CarryLoad,
+    commands::commands::ShopCmd,
+    equipment::Equipment,
+    items::{
+        InventoryItems, ItemAsset, ItemData, ItemId, ItemType, add_to_inventory, count_each,
+        one_per_item, remove_from_stack,
+    },
+    nouns::{CommandReply, Disambiguation, NounPhrase, Picked, Quantity, pick},
+    player::Player,
+    state::{GameState, MainState},
+    ui::update::UpdateMainSectionText,
+    wallet::{Purse, Wallet},
+    world::WorldState,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+pub type ShopId = String;
+
+/// how many lines of the shop log are shown in the shop view.
+const LOG_LINES: usize = 6;
+/// the most of one item that can be bought at once from a shop that never runs out.
+const BULK_LIMIT: usize = 99;
+
+/// something a shop sells.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockEntry {
+    pub item: ItemId,
+    /// what the shop charges for one, in coins. None sells it for what the item is worth.
+    #[serde(default)]
+    pub price: Option<u32>,
+    /// how many the shop has. None never runs out.
+    #[serde(default)]
+    pub quantity: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Asset, TypePath)]
+pub struct ShopAsset {
+    pub name: String,
+    /// what the shopkeeper says when the player walks in.
+    pub greeting: String,
+    pub stock: Vec<StockEntry>,
+    /// the part (0.0 - 1.0) of an items value the shop pays when the player sells it.
+    #[serde(default = "half")]
+    pub buy_back: f32,
+}
+
+fn half() -> f32 {
+    0.5
+}
+
+impl ShopAsset {
+    /// what the shop charges for one of `item`.
+    pub fn price(&self, stock: &StockEntry, item: &ItemAsset) -> u32 {
+        stock.price.unwrap_or(item.value)
+    }
+
+    /// what the shop pays for one of `item`.
+    pub fn offer(&self, item: &ItemAsset) -> u32 {
+        (item.value as f32 * self.buy_back.clamp(0.0, 1.0)).floor() as u32
+    }
+}
+
+#[derive(Debug, Clone, Default, Resource)]
+pub struct Shops(pub HashMap<ShopId, Handle<ShopAsset>>);
+
+/// looks up what a shop is.
+#[derive(SystemParam)]
+pub struct ShopData<'w> {
+    shops: Res<'w, Shops>,
+    assets: Res<'w, Assets<ShopAsset>>,
+}
+
+impl ShopData<'_> {
+    /// the shop, None if it hasn't loaded.
+    pub fn get(&self, shop_id: &ShopId) -> Option<&ShopAsset> {
+        self.assets.get(self.shops.0.get(shop_id)?)
+    }
+}
+
+/// the player at the counter, what they carry & what they can pay with.
+#[derive(SystemParam)]
+struct Customer<'w, 's> {
+    cmds: Commands<'w, 's>,
+    inventory: InventoryItems<'w, 's>,
+    player: Query<'w, 's, &'static mut Equipment, With<Player>>,
+    purse: Purse<'w, 's>,
+    load: CarryLoad<'w, 's>,
+}
+
+/// sent when the player goes in to a shop.
+#[derive(Message, Debug, Clone)]
+pub struct EnterShop {
+    pub shop: ShopId,
+}
+
+/// the shop the player is currently in.
+#[derive(Resource, Debug, Clone)]
+pub struct ShopVisit {
+    pub shop_id: ShopId,
+    pub name: String,
+    /// where to go back to when the player leaves.
+    pub from: GameState,
+    /// what the shopkeeper has told the player, ie. the greeting & the stock list. how buying &
+    /// selling went is shown with the other command results.
+    pub log: Vec<String>,
+}
+
+impl ShopVisit {
+    /// adds to the log, only keeping the lines that are shown.
+    pub fn tell(&mut self, lines: impl IntoIterator<Item = String>) {
+        self.log.extend(lines);
+
+        let old = self.log.len().saturating_sub(LOG_LINES);
+        self.log.drain(..old);
+    }
+}
+
+/// what the player asked to buy or sell. None when they didn't name anything, ie. "sell 3", which
+/// would otherwise pick from everything.
+fn trade_phrase(thing: &[String]) -> Option<NounPhrase> {
+    let phrase = NounPhrase::parse(thing);
+    (!phrase.name.is_empty()).then_some(phrase)
+}
+
+/// one line of the shops stock, ie. "Health Potion  10c  3 left".
+fn stock_line(
+    shop: &ShopAsset,
+    shop_id: &ShopId,
+    world: &WorldState,
+    stock: &StockEntry,
+    asset: &ItemAsset,
+) -> String {
+    let left = match stock.quantity {
+        Some(quantity) => {
+            let left = quantity.saturating_sub(world.bought_from(shop_id, &stock.item));
+
+            if left == 0 {
+                "sold out".to_string()
+            } else {
+                format!("{left} left")
+            }
+        }
+        None => String::new(),
+    };
+
+    format!(
+        "  {: <20}{: >5}c  {left}",
+        asset.name,
+        shop.price(stock, asset)
+    )
+}
+
+/// how many of something the player can buy, limited by what's in stock, what they can afford &
+/// what they can carry.
+pub fn how_many_to_buy(
+    wanted: &NounPhrase,
+    in_stock: Option<u32>,
+    price: u32,
+    coins: u32,
+    fits: usize,
+) -> usize {
+    let in_stock = in_stock.map_or(BULK_LIMIT, |left| left as usize);
+    let affordable = coins.checked_div(price).map_or(usize::MAX, |n| n as usize);
+
+    wanted.how_many(in_stock).min(affordable).min(fits)
+}
+
+#[derive(Clone, Debug)]
+pub struct ShopsPlugin;
+
+impl Plugin for ShopsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<EnterShop>()
+            .add_systems(Update, enter_shop.run_if(in_state(MainState::InGame)))
+            .add_systems(
+                Update,
+                (handle_shop_cmd, render_shop)
+                    .chain()
+                    .run_if(in_state(GameState::Shopping)),
+            )
+            .add_systems(OnExit(GameState::Shopping), leave_shop);
+    }
+}
+
+fn enter_shop(
+    mut cmds: Commands,
+    mut enter_evs: MessageReader<EnterShop>,
+    shops: ShopData,
+    game_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+) {
+    for ev in enter_evs.read() {
+        if !matches!(game_state.get(), GameState::Adventure | GameState::Dungeon) {
+            warn!("can't go in {} from {game_state:?}", ev.shop);
+            continue;
+        }
+
+        let Some(shop) = shops.get(&ev.shop) else {
+            error!("tried to go in unknown shop {:?}", ev.shop);
+            continue;
+        };
+
+        info!("entering shop {}", ev.shop);
+        cmds.insert_resource(ShopVisit {
+            shop_id: ev.shop.clone(),
+            name: shop.name.clone(),
+            from: *game_state.get(),
+            log: vec![shop.greeting.clone()],
+        });
+        result_ev.write(CommandResultEvent::success(format!(
+            "You go in to {}.",
+            shop.name
+        )));
+        next_state.set(GameState::Shopping);
+        break;
+    }
+}
+
+fn handle_shop_cmd(
+    mut shop_cmds: MessageReader<ShopCmd>,
+    mut reply: CommandReply,
+    mut visit: ResMut<ShopVisit>,
+    shops: ShopData,
+    mut world: ResMut<WorldState>,
+    customer: Customer,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(shop) = shops.get(&visit.shop_id) else {
+        return;
+    };
+    let Customer {
+        mut cmds,
+        inventory,
+        mut player,
+        mut purse,
+        load,
+    } = customer;
+    let items = &inventory.items;
+
+    for cmd in shop_cmds.read() {
+        let Ok(mut equipment) = player.single_mut() else {
+            error!("there is no player to go shopping with");
+            continue;
+        };
+
+        match cmd {
+            ShopCmd::Buy { thing } => {
+                let Some(phrase) = trade_phrase(thing) else {
+                    reply.failure("Buy what?");
+                    continue;
+                };
+
+                let for_sale: Vec<(usize, String)> = shop
+                    .stock
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, stock)| Some((i, items.get(&stock.item)?.name.clone())))
+                    .collect();
+                // just which item, how many is worked out below.
+                let which = NounPhrase {
+                    quantity: Quantity::One,
+                    ..phrase.clone()
+                };
+
+                let i = match pick(&which, &for_sale) {
+                    Picked::Things(picked) if !picked.is_empty() => picked[0],
+                    Picked::Ambiguous(options) => {
+                        reply.ask(Disambiguation::new("buy", &phrase, options));
+                        continue;
+                    }
+                    _ => {
+                        reply.failure(format!("They don't sell any {} here.", phrase.name));
+                        continue;
+                    }
+                };
+                let stock = &shop.stock[i];
+                let Some(asset) = items.get(&stock.item) else {
+                    continue;
+                };
+                let price = shop.price(stock, asset);
+                let in_stock = stock.quantity.map(|quantity| {
+                    quantity.saturating_sub(world.bought_from(&visit.shop_id, &stock.item))
+                });
+                let fits = if asset.weight > 0.0 {
+                    ((load.capacity() - load.weight()) / asset.weight + f32::EPSILON)
+                        .floor()
+                        .max(0.0) as usize
+                } else {
+                    usize::MAX
+                };
+                let wanted = phrase.how_many(in_stock.map_or(BULK_LIMIT, |left| left as usize));
+                let count = how_many_to_buy(&phrase, in_stock, price, purse.coins(), fits);
+
+                if in_stock == Some(0) {
+                    reply.failure(format!("They're sold out of {}.", asset.name));
+                    continue;
+                }
+
+                if count == 0 && purse.coins() < price {
+                    reply.failure(format!(
+                        "The {} costs {price} coins, you only have {}.",
+                        asset.name,
+                        purse.coins()
+                    ));
+                    continue;
+                }
+
+                if count == 0 {
+                    reply.failure(format!("You can't carry another {}.", asset.name));
+                    continue;
+                }
+
+                let cost = price * count as u32;
+                let bought = vec![stock.item.clone(); count];
+                let described = items.describe(&bought).unwrap_or_default();
+
+                if !purse.spend(cost, format!("bought {described}")) {
+                    continue;
+                }
+
+                add_to_inventory(
+                    &mut cmds,
+                    inventory.entries.iter(),
+                    &stock.item,
+                    asset,
+                    count,
+                    &[],
+                );
+
+                if stock.quantity.is_some() {
+                    *world
+                        .shops
+                        .entry(visit.shop_id.clone())
+                        .or_default()
+                        .bought
+                        .entry(stock.item.clone())
+                        .or_default() += count as u32;
+                }
+
+                let mut line = format!("You buy: {described} for {cost} coins.");
+
+                if count < wanted {
+                    line.push_str(" That's as many as you could manage.");
+                }
+
+                reply.success(line);
+            }
+            ShopCmd::Sell { thing } => {
+                let Some(phrase) = trade_phrase(thing) else {
+                    reply.failure("Sell what?");
+                    continue;
+                };
+                // one per item so "sell 3 clubs" can sell part of a stack. equipped & key items
+                // stay with the player.
+                let sellable: Vec<((Entity, ItemId), String)> = one_per_item(
+                    inventory
+                        .iter()
+                        .filter(|(entity, _, _)| !equipment.is_equipped(*entity))
+                        .filter_map(|(entity, entry, asset)| {
+                            if matches!(asset.item_data, ItemType::KeyItem { .. }) {
+                                return None;
+                            }
+
+                            Some((
+                                (entity, entry.asset_path.clone()),
+                                entry.count,
+                                asset.name.clone(),
+                            ))
+                        }),
+                );
+
+                let sold = match pick(&phrase, &sellable) {
+                    Picked::Things(sold) if !sold.is_empty() => sold,
+                    Picked::Ambiguous(options) => {
+                        reply.ask(Disambiguation::new("sell", &phrase, options));
+                        continue;
+                    }
+                    _ => {
+                        reply.failure(format!("You don't have a {} to sell.", phrase.name));
+                        continue;
+                    }
+                };
+
+                let earned: u32 = sold
+                    .iter()
+                    .filter_map(|(_, item)| items.get(item))
+                    .map(|asset| shop.offer(asset))
+                    .sum();
+
+                for (entity, count) in count_each(sold.iter().map(|(entity, _)| *entity)) {
+                    if let Ok((_, entry)) = inventory.entries.get(entity)
+                        && remove_from_stack(&mut cmds, entity, entry, count)
+                    {
+                        equipment.unequip(entity);
+                    }
+                }
+
+                let sold: Vec<ItemId> = sold.into_iter().map(|(_, item)| item).collect();
+                let described = items.describe(&sold).unwrap_or_default();
+                purse.earn(earned, format!("sold {described}"));
+
+                reply.success(format!("You sell: {described} for {earned} coins."));
+            }
+            ShopCmd::List => {
+                let mut lines = vec!["For sale:".to_string()];
+                lines.extend(shop.stock.iter().filter_map(|stock| {
+                    let asset = items.get(&stock.item)?;
+                    let line = stock_line(shop, &visit.shop_id, &world, stock, asset);
+                    Some(format!("{} {}", line.trim_end(), asset.description))
+                }));
+
+                // the same things selling would take.
+                let mut offers: Vec<String> = inventory
+                    .iter()
+                    .filter(|(entity, _, asset)| {
+                        !equipment.is_equipped(*entity)
+                            && !matches!(asset.item_data, ItemType::KeyItem { .. })
+                    })
+                    .map(|(_, _, asset)| format!("{} ({}c)", asset.name, shop.offer(asset)))
+                    .collect();
+                offers.sort();
+                offers.dedup();
+
+                if offers.is_empty() {
+                    lines.push("You have nothing they'd buy.".into());
+                } else {
+                    lines.push(format!("They'd pay: {}", offers.join(", ")));
+                }
+
+                visit.tell(lines);
+            }
+            ShopCmd::Leave => next_state.set(visit.from),
+        }
+    }
+}
+
+/// draws the shop view in the main text panel.
+fn render_shop(
+    visit: Res<ShopVisit>,
+    shops: ShopData,
+    world: Res<WorldState>,
+    mut new_zone_evs: MessageReader<NewZone>,
+    player: Query<Ref<Wallet>, With<Player>>,
+    items: ItemData,
+    mut update_event: MessageWriter<UpdateMainSectionText>,
+) {
+    // NewZone is sent when the main screen is redrawn (ie. when coming back from the inventory).
+    let redraw = new_zone_evs.read().count() > 0;
+
+    let Ok(wallet) = player.single() else {
+        return;
+    };
+
+    if !visit.is_changed() && !wallet.is_changed() && !redraw {
+        return;
+    }
+
+    let Some(shop) = shops.get(&visit.shop_id) else {
+        return;
+    };
+
+    let stock: Vec<String> = shop
+        .stock
+        .iter()
+        .filter_map(|stock| {
+            let asset = items.get(&stock.item)?;
+            Some(stock_line(shop, &visit.shop_id, &world, stock, asset))
+        })
+        .collect();
+
+    let log = visit.log.join("\n");
+
+    update_event.write(UpdateMainSectionText(format!(
+        "=== {} ===\n\nYou have {} coins.\n\n{}\n\n{log}\n\n[buy <item> | sell <item> | list | leave]",
+        visit.name,
+        wallet.coins(),
+        stock.join("\n"),
+    )));
+}
+
+/// tidies up after the player walks out of a shop.
+fn leave_shop(
+    mut cmds: Commands,
+    visit: Option<Res<ShopVisit>>,
+    mut new_zone_ev: MessageWriter<NewZone>,
+    mut result_ev: MessageWriter<CommandResultEvent>,
+) {
+    if let Some(visit) = visit {
+        info!("left shop {}", visit.shop_id);
+        result_ev.write(CommandResultEvent::success(format!(
+            "You leave {}.",
+            visit.name
+        )));
+    }
+
+    cmds.remove_resource::<ShopVisit>();
+    new_zone_ev.write_default();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BULK_LIMIT, how_many_to_buy, trade_phrase};
+    use crate::nouns::NounPhrase;
+
+    #[test]
+    fn buying_is_limited_by_stock_coins_and_weight() {
+        let phrase = |words: &[&str]| {
+            NounPhrase::parse(
+                &words
+                    .iter()
+                    .map(|word| word.to_string())
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(how_many_to_buy(&phrase(&["potion"]), None, 10, 30, 5), 1);
+        assert_eq!(
+            how_many_to_buy(&phrase(&["5", "potions"]), Some(2), 10, 30, 5),
+            2
+        );
+        assert_eq!(
+            how_many_to_buy(&phrase(&["5", "potions"]), None, 10, 30, 5),
+            3
+        );
+        assert_eq!(
+            how_many_to_buy(&phrase(&["all", "potions"]), None, 1, 30, 4),
+            4
+        );
+        assert_eq!(how_many_to_buy(&phrase(&["potion"]), None, 10, 5, 5), 0);
+        assert_eq!(
+            how_many_to_buy(&phrase(&["all", "stones"]), None, 0, 0, usize::MAX),
+            BULK_LIMIT
+        );
+    }
+
+    #[test]
+    fn trading_needs_a_name() {
+        let words =
+            |text: &str| -> Vec<String> { text.split_whitespace().map(String::from).collect() };
+
+        // "sell 3" would otherwise pick 3 of anything.
+        assert!(trade_phrase(&words("3")).is_none());
+        assert!(trade_phrase(&words("the")).is_none());
+        assert!(trade_phrase(&words("all")).is_none());
+        assert_eq!(
+            trade_phrase(&words("3 clubs")).map(|phrase| phrase.name),
+            Some("clubs".into())
+        );
+    }
+}
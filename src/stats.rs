@@ -0,0 +1,181 @@
+use crate::{
+    items::{Amount, Stat},
+    player::{BaseStats, CombatStats, EffectiveStats, Health, Player},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// where a stat modifier comes from. layers are applied in this order, so a status effect that
+/// halves your attack also halves what your gear & buffs added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModifierLayer {
+    /// the weapons & armor the player has equipped.
+    Equipment,
+    /// timed buffs, ie. from potions.
+    Buffs,
+    /// status ailments.
+    Status,
+    /// carrying a heavy load.
+    Load,
+}
+
+impl ModifierLayer {
+    pub const ALL: [ModifierLayer; 4] = [Self::Equipment, Self::Buffs, Self::Status, Self::Load];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatModifier {
+    pub stat: Stat,
+    pub amount: Amount,
+}
+
+/// everything currently changing the players stats, grouped by layer. each system that owns a
+/// layer replaces it wholesale when its source changes.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct StatModifiers {
+    pub equipment: Vec<StatModifier>,
+    pub buffs: Vec<StatModifier>,
+    pub status: Vec<StatModifier>,
+    pub load: Vec<StatModifier>,
+}
+
+impl StatModifiers {
+    pub fn layer(&self, layer: ModifierLayer) -> &[StatModifier] {
+        match layer {
+            ModifierLayer::Equipment => &self.equipment,
+            ModifierLayer::Buffs => &self.buffs,
+            ModifierLayer::Status => &self.status,
+            ModifierLayer::Load => &self.load,
+        }
+    }
+
+    pub fn layer_mut(&mut self, layer: ModifierLayer) -> &mut Vec<StatModifier> {
+        match layer {
+            ModifierLayer::Equipment => &mut self.equipment,
+            ModifierLayer::Buffs => &mut self.buffs,
+            ModifierLayer::Status => &mut self.status,
+            ModifierLayer::Load => &mut self.load,
+        }
+    }
+}
+
+/// applies one layer of modifiers: all the fixed amounts are added, then the percentages of the
+/// layer are summed and applied on top. ie. +2 and +50% on an attack of 10 makes (10 + 2) * 1.5.
+pub fn apply_layer(stats: &CombatStats, modifiers: &[StatModifier]) -> CombatStats {
+    let mut added = CombatStats::default();
+    let mut percent = CombatStats::default();
+
+    for modifier in modifiers {
+        match modifier.amount {
+            Amount::Fixed(value) => *added.get_mut(&modifier.stat) += value as f32,
+            Amount::Percent(value) => *percent.get_mut(&modifier.stat) += value,
+        }
+    }
+
+    let mut stats = *stats;
+
+    for stat in Stat::ALL {
+        let value = stats.get_mut(&stat);
+        *value = (*value + added.get(&stat)) * (1.0 + percent.get(&stat));
+    }
+
+    stats
+}
+
+/// the players stats after every layer of modifiers, in `ModifierLayer` order. no stat goes below
+/// zero, and max HP never goes below 1.
+pub fn effective_stats(base: &CombatStats, modifiers: &StatModifiers) -> CombatStats {
+    let mut stats = ModifierLayer::ALL.into_iter().fold(*base, |stats, layer| {
+        apply_layer(&stats, modifiers.layer(layer))
+    });
+
+    for stat in Stat::ALL {
+        let value = stats.get_mut(&stat);
+        *value = value.max(0.0);
+    }
+
+    stats.hp = stats.hp.max(1.0);
+
+    stats
+}
+
+/// keeps the players effective stats in line with their base stats & modifiers.
+pub fn update_effective_stats(
+    mut player: Query<(&BaseStats, &StatModifiers, &mut EffectiveStats, &mut Health), With<Player>>,
+) {
+    let Ok((base, modifiers, mut effective, mut health)) = player.single_mut() else {
+        return;
+    };
+
+    let stats = effective_stats(&base.0, modifiers);
+
+    // losing max HP (ie. taking off armor) can't leave you with more HP than your max.
+    if effective.set_if_neq(EffectiveStats(stats)) && health.0 > stats.hp {
+        health.0 = stats.hp;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StatModifier, StatModifiers, effective_stats};
+    use crate::{
+        items::{Amount, Stat},
+        player::CombatStats,
+    };
+
+    fn base() -> CombatStats {
+        CombatStats {
+            hp: 30.0,
+            attack: 10.0,
+            defence: 4.0,
+            ..Default::default()
+        }
+    }
+
+    fn modifier(stat: Stat, amount: Amount) -> StatModifier {
+        StatModifier { stat, amount }
+    }
+
+    #[test]
+    fn fixed_is_added_before_percent() {
+        let modifiers = StatModifiers {
+            equipment: vec![
+                modifier(Stat::Attack, Amount::Percent(0.5)),
+                modifier(Stat::Attack, Amount::Fixed(2)),
+            ],
+            ..Default::default()
+        };
+
+        let stats = effective_stats(&base(), &modifiers);
+        assert_eq!(stats.attack, 18.0);
+        assert_eq!(stats.defence, 4.0, "untouched stats stay the same");
+    }
+
+    #[test]
+    fn layers_apply_in_order() {
+        let modifiers = StatModifiers {
+            equipment: vec![modifier(Stat::Attack, Amount::Fixed(10))],
+            buffs: vec![modifier(Stat::Attack, Amount::Percent(0.5))],
+            status: vec![modifier(Stat::Attack, Amount::Percent(-0.5))],
+            load: vec![],
+        };
+
+        // ((10 + 10) * 1.5) * 0.5, not (10 + 10) * (1 + 0.5 - 0.5).
+        assert_eq!(effective_stats(&base(), &modifiers).attack, 15.0);
+    }
+
+    #[test]
+    fn stats_never_go_negative() {
+        let modifiers = StatModifiers {
+            status: vec![
+                modifier(Stat::Defence, Amount::Fixed(-100)),
+                modifier(Stat::HP, Amount::Percent(-2.0)),
+            ],
+            ..Default::default()
+        };
+
+        let stats = effective_stats(&base(), &modifiers);
+        assert_eq!(stats.defence, 0.0);
+        assert_eq!(stats.hp, 1.0);
+    }
+}
@@ -1,15 +1,17 @@
 use crate::{
     commands::{
         BadCommand,
-        commands::{GameCmd, SlashCmd},
+        commands::{BattleCmd, Direction, GameCmd, ShopCmd, SlashCmd},
     },
     handle_player_move::{compass_update, set_main_body},
     menu_screens::MenuScreensPlugin,
+    nouns::PendingQuestion,
     state::{GameState, MainScreenState, MainState},
 };
 use bevy::{
     camera::Viewport,
     color::palettes::{css::GREEN, tailwind::AMBER_500},
+    ecs::system::SystemParam,
     prelude::*,
     window::WindowResized,
 };
@@ -44,35 +46,9 @@ pub struct LookTextBody;
 #[derive(Component)]
 pub struct CmdPrompt;
 
+/// one of the points on the compass, lit up when the player can go that way.
 #[derive(Component)]
-pub struct CompassUpText;
-
-#[derive(Component)]
-pub struct CompassDownText;
-
-#[derive(Component)]
-pub struct CompassNorthText;
-
-#[derive(Component)]
-pub struct CompassSouthText;
-
-#[derive(Component)]
-pub struct CompassEastText;
-
-#[derive(Component)]
-pub struct CompassWestText;
-
-#[derive(Component)]
-pub struct CompassNorthEastText;
-
-#[derive(Component)]
-pub struct CompassNorthWestText;
-
-#[derive(Component)]
-pub struct CompassSouthEastText;
-
-#[derive(Component)]
-pub struct CompassSouthWestText;
+pub struct CompassText(pub Direction);
 
 /// A marker component for our shapes so we can query them separately from the ground plane
 #[derive(Component)]
@@ -119,10 +95,13 @@ impl Plugin for TextUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<GameCmd>()
             .add_message::<SlashCmd>()
+            .add_message::<BattleCmd>()
+            .add_message::<ShopCmd>()
             .add_message::<BadCommand>()
             .add_message::<UpdateMainSectionText>()
             .add_message::<UpdateLookSectionText>()
             .init_resource::<CmdHistory>()
+            .init_resource::<PendingQuestion>()
             .add_plugins(MenuScreensPlugin)
             .add_systems(OnEnter(MainState::InGame), (camera_setup, spawn_cube))
             .add_systems(
@@ -135,7 +114,10 @@ impl Plugin for TextUiPlugin {
                 Update,
                 (
                     listener,
-                    set_main_body.run_if(in_state(MainScreenState::MainGame)),
+                    set_main_body
+                        .run_if(in_state(MainScreenState::MainGame))
+                        .run_if(not(in_state(GameState::Battle)))
+                        .run_if(not(in_state(GameState::Shopping))),
                     update_cmd_history,
                     navigate_cmd_history.run_if(in_state(MainScreenState::MainGame)),
                 )
@@ -146,17 +128,63 @@ impl Plugin for TextUiPlugin {
     }
 }
 
+/// where typed commands go once they're parsed.
+#[derive(SystemParam)]
+struct CommandWriters<'w> {
+    cmd_event: MessageWriter<'w, GameCmd>,
+    slash_cmd_event: MessageWriter<'w, SlashCmd>,
+    battle_cmd_event: MessageWriter<'w, BattleCmd>,
+    shop_cmd_event: MessageWriter<'w, ShopCmd>,
+    bad_cmd_event: MessageWriter<'w, BadCommand>,
+}
+
 fn listener(
     mut events: MessageReader<TextInputSubmitMessage>,
-    mut cmd_event: MessageWriter<GameCmd>,
-    mut slash_cmd_event: MessageWriter<SlashCmd>,
-    mut bad_cmd_event: MessageWriter<BadCommand>,
+    writers: CommandWriters,
+    game_state: Res<State<GameState>>,
+    mut question: ResMut<PendingQuestion>,
 ) {
+    let CommandWriters {
+        mut cmd_event,
+        mut slash_cmd_event,
+        mut battle_cmd_event,
+        mut shop_cmd_event,
+        mut bad_cmd_event,
+    } = writers;
+
     for event in events.read() {
         info!("Player submitted command: {}", event.value);
-        let cmd = event.value.clone();
+        let mut cmd = event.value.clone();
+
+        // an answer to "which do you mean?" gets turned back into the full command.
+        if !cmd.starts_with("/")
+            && let Some(ask) = question.0.take()
+            && let Some(answer) = ask.answer(&cmd)
+        {
+            cmd = answer;
+        }
 
-        if !cmd.starts_with("/") {
+        if !cmd.starts_with("/") && *game_state.get() == GameState::Battle {
+            // only battle commands work in the middle of a fight.
+            match BattleCmd::try_parse_from(cmd.split_whitespace()) {
+                Ok(command) => {
+                    battle_cmd_event.write(command);
+                }
+                Err(e) => {
+                    bad_cmd_event.write(BadCommand::new(&cmd, &e));
+                }
+            }
+        } else if !cmd.starts_with("/") && *game_state.get() == GameState::Shopping {
+            // same goes for shopping.
+            match ShopCmd::try_parse_from(cmd.split_whitespace()) {
+                Ok(command) => {
+                    shop_cmd_event.write(command);
+                }
+                Err(e) => {
+                    bad_cmd_event.write(BadCommand::new(&cmd, &e));
+                }
+            }
+        } else if !cmd.starts_with("/") {
             // parse to cmd
             let command = GameCmd::try_parse_from(cmd.split_whitespace());
 
@@ -165,9 +193,9 @@ fn listener(
                     // fire command evvent
                     cmd_event.write(command);
                 }
-                Err(_e) => {
+                Err(e) => {
                     // fire unrecognized command event
-                    bad_cmd_event.write_default();
+                    bad_cmd_event.write(BadCommand::new(&cmd, &e));
                 }
             }
         } else {
@@ -179,9 +207,9 @@ fn listener(
                     // fire shash command event
                     slash_cmd_event.write(command);
                 }
-                Err(_e) => {
+                Err(e) => {
                     // fire unrecognized command event
-                    bad_cmd_event.write_default();
+                    bad_cmd_event.write(BadCommand::new(&cmd, &e));
                 }
             }
         }
@@ -213,7 +241,7 @@ fn camera_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 
     commands.spawn((
-        Camera2d::default(),
+        Camera2d,
         Transform::from_xyz(1_000.0, 1_000.0, 1_000.0)
             .looking_at(Vec3::new(1_000.0, 1_000.0, 1_000.0), Vec3::Y),
         Camera {
@@ -483,7 +511,7 @@ fn spawn_compass(parent: &mut ChildSpawnerCommands<'_>, text_font: TextFont) {
                         Text::new("U"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassUpText,
+                        CompassText(Direction::Up),
                     ));
                     // parent.spawn(
                     //     (
@@ -496,7 +524,7 @@ fn spawn_compass(parent: &mut ChildSpawnerCommands<'_>, text_font: TextFont) {
                         Text::new("D"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassDownText,
+                        CompassText(Direction::Down),
                     ));
                 });
             parent
@@ -509,19 +537,19 @@ fn spawn_compass(parent: &mut ChildSpawnerCommands<'_>, text_font: TextFont) {
                         Text::new("NW"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassNorthWestText,
+                        CompassText(Direction::NorthWest),
                     ));
                     parent.spawn((
                         Text::new("W"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassWestText,
+                        CompassText(Direction::West),
                     ));
                     parent.spawn((
                         Text::new("SW"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassSouthWestText,
+                        CompassText(Direction::SouthWest),
                     ));
                 });
             parent
@@ -534,7 +562,7 @@ fn spawn_compass(parent: &mut ChildSpawnerCommands<'_>, text_font: TextFont) {
                         Text::new("N"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassNorthText,
+                        CompassText(Direction::North),
                     ));
                     parent.spawn((
                         Text::new("*"),
@@ -545,7 +573,7 @@ fn spawn_compass(parent: &mut ChildSpawnerCommands<'_>, text_font: TextFont) {
                         Text::new("S"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassSouthText,
+                        CompassText(Direction::South),
                     ));
                 });
             parent
@@ -558,19 +586,19 @@ fn spawn_compass(parent: &mut ChildSpawnerCommands<'_>, text_font: TextFont) {
                         Text::new("NE"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassNorthEastText,
+                        CompassText(Direction::NorthEast),
                     ));
                     parent.spawn((
                         Text::new("E"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassEastText,
+                        CompassText(Direction::East),
                     ));
                     parent.spawn((
                         Text::new("SE"),
                         text_font.clone().with_font_size(25.0),
                         TextLayout::new_with_justify(Justify::Center),
-                        CompassSouthEastText,
+                        CompassText(Direction::SouthEast),
                     ));
                 });
         });
@@ -626,7 +654,7 @@ fn navigate_cmd_history(
     mut text_input: Query<&mut TextInputValue>,
 ) {
     if let Ok(ref mut text_input) = text_input.single_mut()
-        && history.history.len() > 0
+        && !history.history.is_empty()
     {
         if keys.just_pressed(KeyCode::ArrowUp) {
             if history.line_storage.is_some() {
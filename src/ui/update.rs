@@ -1,5 +1,9 @@
 use super::{LookTextBody, MainTextBody};
-use bevy::prelude::*;
+use crate::{CommandResultEvent, flavor::FlavorLibrary};
+use bevy::{
+    color::palettes::tailwind::{AMBER_500, RED_400},
+    prelude::*,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Message, PartialEq, Eq, PartialOrd, Ord)]
@@ -32,15 +36,64 @@ pub fn update_main_section(
 // }
 
 pub fn update_look_section(
+    mut cmds: Commands,
     mut events: MessageReader<UpdateLookSectionText>,
-    mut text: Query<&mut Text, With<LookTextBody>>,
+    mut text: Query<(Entity, &mut Text, &mut TextColor), With<LookTextBody>>,
 ) {
     for event in events.read() {
         // warn!("{event:?}");
         let new_text = event.0.clone();
 
-        if let Err(e) = text.single_mut().map(|mut text| text.0 = new_text) {
+        if let Err(e) = text.single_mut().map(|(entity, mut text, mut color)| {
+            text.0 = new_text;
+            color.0 = AMBER_500.into();
+            // drop the lines left over from `show_command_results`.
+            cmds.entity(entity).despawn_children();
+        }) {
             error!("setting look text body text resulted in: {e}");
         }
     }
 }
+
+/// shows the player how their commands went in the text marked with `T`. each result gets its own
+/// line, failures are shown in red.
+pub fn show_command_results<T: Component>(
+    mut cmds: Commands,
+    mut events: MessageReader<CommandResultEvent>,
+    mut text: Query<(Entity, &mut Text, &TextFont), With<T>>,
+    mut flavor: FlavorLibrary,
+) {
+    let mut lines = Vec::new();
+
+    for CommandResultEvent(result) in events.read() {
+        let (Ok(flavor_text) | Err(flavor_text)) = result;
+
+        let Some(line) = flavor.resolve(flavor_text) else {
+            error!("there is no flavor text for {flavor_text:?}");
+            continue;
+        };
+
+        let color = if result.is_err() { RED_400 } else { AMBER_500 };
+        lines.push((line, color));
+    }
+
+    if lines.is_empty() {
+        return;
+    }
+
+    let Ok((entity, mut text, font)) = text.single_mut() else {
+        error!("there is no text to show command results in");
+        return;
+    };
+
+    // every line is a span of its own so it can have its own color.
+    text.0 = String::new();
+    cmds.entity(entity)
+        .despawn_children()
+        .with_children(|parent| {
+            for (i, (line, color)) in lines.into_iter().enumerate() {
+                let line = if i == 0 { line } else { format!("\n\n{line}") };
+                parent.spawn((TextSpan::new(line), font.clone(), TextColor(color.into())));
+            }
+        });
+}
@@ -0,0 +1,149 @@
+use crate::{Notification, NotificationLevel, player::Player, state::MainState};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// how many coins a new player starts out with.
+pub const STARTING_COINS: u32 = 30;
+
+/// the coins the player is carrying. only changed through `Purse`, so every change is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Component)]
+pub struct Wallet(u32);
+
+impl Default for Wallet {
+    fn default() -> Self {
+        Self(STARTING_COINS)
+    }
+}
+
+impl Wallet {
+    pub fn coins(&self) -> u32 {
+        self.0
+    }
+
+    /// adds coins, returning how many were actually added. (the wallet can't overflow)
+    fn add(&mut self, amount: u32) -> u32 {
+        let before = self.0;
+        self.0 = self.0.saturating_add(amount);
+
+        self.0 - before
+    }
+
+    /// takes coins out, unless there aren't enough.
+    fn take(&mut self, amount: u32) -> bool {
+        match self.0.checked_sub(amount) {
+            Some(left) => {
+                self.0 = left;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// sent every time the players coins go up or down.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct CoinsChanged {
+    /// how much was gained (positive) or lost (negative).
+    pub change: i64,
+    /// the coins the player has now.
+    pub coins: u32,
+    /// what the coins were for, ie. "sold Goblin Club".
+    pub reason: String,
+}
+
+/// the one way to give the player coins or take them away.
+#[derive(SystemParam)]
+pub struct Purse<'w, 's> {
+    wallet: Query<'w, 's, &'static mut Wallet, With<Player>>,
+    changed: MessageWriter<'w, CoinsChanged>,
+}
+
+impl Purse<'_, '_> {
+    /// how many coins the player has.
+    pub fn coins(&self) -> u32 {
+        self.wallet
+            .single()
+            .map(|wallet| wallet.coins())
+            .unwrap_or_default()
+    }
+
+    pub fn earn(&mut self, amount: u32, reason: impl Into<String>) {
+        let Ok(mut wallet) = self.wallet.single_mut() else {
+            return;
+        };
+
+        let added = wallet.add(amount);
+
+        if added > 0 {
+            self.changed.write(CoinsChanged {
+                change: added as i64,
+                coins: wallet.coins(),
+                reason: reason.into(),
+            });
+        }
+    }
+
+    /// pays `amount`, returns false (and takes nothing) if the player can't afford it.
+    pub fn spend(&mut self, amount: u32, reason: impl Into<String>) -> bool {
+        let Ok(mut wallet) = self.wallet.single_mut() else {
+            return false;
+        };
+
+        if !wallet.take(amount) {
+            return false;
+        }
+
+        if amount > 0 {
+            self.changed.write(CoinsChanged {
+                change: -(amount as i64),
+                coins: wallet.coins(),
+                reason: reason.into(),
+            });
+        }
+
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WalletPlugin;
+
+impl Plugin for WalletPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<CoinsChanged>()
+            .add_systems(Update, report_coins.run_if(in_state(MainState::InGame)));
+    }
+}
+
+/// logs every change to the players coins & lets them know with a notification.
+fn report_coins(
+    mut changed_evs: MessageReader<CoinsChanged>,
+    mut notification_ev: MessageWriter<Notification>,
+) {
+    for ev in changed_evs.read() {
+        info!("coins {:+} ({}), now {}", ev.change, ev.reason, ev.coins);
+        notification_ev.write(Notification::new(
+            NotificationLevel::Normal,
+            format!("{:+} coins, {}. ({} total)", ev.change, ev.reason, ev.coins),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Wallet;
+
+    #[test]
+    fn wallet_never_goes_negative() {
+        let mut wallet = Wallet(10);
+
+        assert!(!wallet.take(11));
+        assert_eq!(wallet.coins(), 10);
+        assert!(wallet.take(10));
+        assert_eq!(wallet.coins(), 0);
+        assert_eq!(wallet.add(5), 5);
+
+        let mut full = Wallet(u32::MAX - 1);
+        assert_eq!(full.add(5), 1);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    HashMap,
+    items::ItemId,
+    shops::ShopId,
+    zones::{ZoneAsset, ZoneId},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// an item lying around in a zone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "SavedGroundItem")]
+pub struct GroundItem {
+    pub item: ItemId,
+    /// the durability that was left on it when the player dropped it. None when it's like new.
+    pub durability: Option<u32>,
+}
+
+impl GroundItem {
+    pub fn new(item: impl Into<ItemId>) -> Self {
+        Self {
+            item: item.into(),
+            durability: None,
+        }
+    }
+}
+
+/// saves from before dropped items kept their durability only have the item.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedGroundItem {
+    Item(ItemId),
+    Dropped {
+        item: ItemId,
+        #[serde(default)]
+        durability: Option<u32>,
+    },
+}
+
+impl From<SavedGroundItem> for GroundItem {
+    fn from(saved: SavedGroundItem) -> Self {
+        match saved {
+            SavedGroundItem::Item(item) => Self::new(item),
+            SavedGroundItem::Dropped { item, durability } => Self { item, durability },
+        }
+    }
+}
+
+/// the parts of a zone that can change while playing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZoneState {
+    /// items currently lying around in the zone, including any the player dropped.
+    pub items: Vec<GroundItem>,
+}
+
+impl From<&ZoneAsset> for ZoneState {
+    fn from(zone: &ZoneAsset) -> Self {
+        Self {
+            items: zone.items.iter().cloned().map(GroundItem::new).collect(),
+        }
+    }
+}
+
+/// the parts of a shop that can change while playing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShopState {
+    /// how many of each item the player has bought, counted against the shops limited stock.
+    pub bought: HashMap<ItemId, u32>,
+}
+
+/// the runtime state of every zone the player has changed. zones that are not in here are still
+/// exactly how their `ZoneAsset` describes them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
+#[serde(default)]
+pub struct WorldState {
+    pub zones: HashMap<ZoneId, ZoneState>,
+    /// shops the player has bought from.
+    pub shops: HashMap<ShopId, ShopState>,
+}
+
+impl WorldState {
+    /// the state of a zone, for changing. it is copied from the zone asset on first use.
+    pub fn zone_mut(&mut self, zone_id: &ZoneId, zone: &ZoneAsset) -> &mut ZoneState {
+        self.zones
+            .entry(zone_id.clone())
+            .or_insert_with(|| ZoneState::from(zone))
+    }
+
+    /// how many of an item the player has bought from a shop.
+    pub fn bought_from(&self, shop_id: &ShopId, item_id: &ItemId) -> u32 {
+        self.shops
+            .get(shop_id)
+            .and_then(|shop| shop.bought.get(item_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// the items currently in a zone.
+    pub fn items_in(&self, zone_id: &ZoneId, zone: &ZoneAsset) -> Vec<GroundItem> {
+        self.zones
+            .get(zone_id)
+            .map(|state| state.items.clone())
+            .unwrap_or_else(|| ZoneState::from(zone).items)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GroundItem, WorldState, ZoneState};
+    use crate::zones::{ZoneAsset, ZoneId};
+
+    #[test]
+    fn zones_are_copied_on_first_change() {
+        let zone = ZoneAsset {
+            items: vec!["club".into(), "potion".into()],
+            ..Default::default()
+        };
+        let gate = "gate".to_string();
+        let mut world = WorldState::default();
+        let items_in = |world: &WorldState, zone_id: &ZoneId| -> Vec<String> {
+            world
+                .items_in(zone_id, &zone)
+                .into_iter()
+                .map(|on_ground| on_ground.item)
+                .collect()
+        };
+
+        assert_eq!(items_in(&world, &gate), ["club", "potion"]);
+
+        world
+            .zone_mut(&gate, &zone)
+            .items
+            .retain(|on_ground| on_ground.item != "club");
+        assert_eq!(items_in(&world, &gate), ["potion"]);
+
+        world
+            .zone_mut(&gate, &zone)
+            .items
+            .push(GroundItem::new("rock"));
+        assert_eq!(
+            items_in(&world, &gate),
+            ["potion", "rock"],
+            "the asset is only copied the first time"
+        );
+        assert_eq!(
+            items_in(&world, &"tavern".to_string()),
+            ["club", "potion"],
+            "other zones are untouched"
+        );
+    }
+
+    #[test]
+    fn dropped_items_keep_their_durability() {
+        let zone = ZoneState {
+            items: vec![
+                GroundItem::new("potion"),
+                GroundItem {
+                    item: "club".into(),
+                    durability: Some(4),
+                },
+            ],
+        };
+        let saved = ron::to_string(&zone).unwrap();
+        let loaded: ZoneState = ron::from_str(&saved).unwrap();
+        assert_eq!(loaded.items, zone.items);
+
+        let old_save: ZoneState = ron::from_str(r#"(items: ["potion"])"#).unwrap();
+        assert_eq!(old_save.items, [GroundItem::new("potion")]);
+    }
+}
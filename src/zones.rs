@@ -1,20 +1,23 @@
 use super::commands::commands::Direction;
-use crate::{HashMap, items::ItemId};
-use bevy::prelude::*;
+use crate::{HashMap, items::ItemId, mobs::MobId, nouns::match_names, shops::ShopId};
+use bevy::{ecs::system::SystemParam, prelude::*};
 use serde::{Deserialize, Serialize};
 
 pub type ZoneId = String;
 pub type FlavorTextId = u64;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FlavorTextType {
     Help,
     // ZoneDescription,
     // ZoneInspection,
     ItemDescription,
     SpellDescription,
-    // ItemDescription,
-    // ItemDescription,
+    /// the player tried to walk somewhere there is no exit. "{direction}" is replaced with where
+    /// they tried to go.
+    CantGo,
+    /// the player tried to take something from an empty zone.
+    NothingToTake,
 }
 
 // #[derive(Debug, Serialize, Deserialize, Clone, Event)]
@@ -52,9 +55,26 @@ pub struct ZoneAsset {
     /// the server response
     pub examine: Option<String>,
     pub connections: HashMap<Direction, String>,
+    /// the chance (0.0 - 1.0) that a mob shows up each turn the player spends in the zone.
     pub mob_spawn_rate: f32,
+    /// the mobs that can show up in this zone. picked from based on `MobAsset::spawn_rate`.
+    #[serde(default)]
+    pub mobs: Vec<MobId>,
     /// items in the zone that can be taken by the player
     pub items: Vec<ItemId>,
+    /// places the player can "go in", by the name they're typed as. ie. `{"store": "shops/..."}`
+    #[serde(default)]
+    pub places: HashMap<String, ShopId>,
+}
+
+impl ZoneAsset {
+    /// the shop the player means by "go in {name}", the name only has to be partly typed out.
+    pub fn place(&self, name: &str) -> Option<&ShopId> {
+        match match_names(name, self.places.keys().map(String::as_str)).as_slice() {
+            [place] => self.places.get(*place),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
@@ -62,3 +82,36 @@ pub struct Location(pub ZoneId);
 
 #[derive(Debug, Clone, Default, Resource)]
 pub struct Zones(pub HashMap<ZoneId, Handle<ZoneAsset>>);
+
+/// looks up what a zone is.
+#[derive(SystemParam)]
+pub struct ZoneData<'w> {
+    zones: Res<'w, Zones>,
+    assets: Res<'w, Assets<ZoneAsset>>,
+}
+
+impl ZoneData<'_> {
+    /// the zone, None if it hasn't loaded.
+    pub fn get(&self, zone_id: &ZoneId) -> Option<&ZoneAsset> {
+        self.assets.get(self.zones.0.get(zone_id)?)
+    }
+
+    /// true if the engine knows of the zone, loaded or not.
+    pub fn contains(&self, zone_id: &ZoneId) -> bool {
+        self.zones.0.contains_key(zone_id)
+    }
+}
+
+/// the zone the player is in.
+#[derive(SystemParam)]
+pub struct CurrentZone<'w> {
+    pub location: Res<'w, Location>,
+    pub zones: ZoneData<'w>,
+}
+
+impl CurrentZone<'_> {
+    /// None if the player is somewhere the engine doesn't know about.
+    pub fn get(&self) -> Option<&ZoneAsset> {
+        self.zones.get(&self.location.0)
+    }
+}